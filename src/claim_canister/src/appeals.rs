@@ -0,0 +1,454 @@
+use candid::{Nat, Principal};
+use sha2::{Digest, Sha256};
+
+use crate::claims::{exceeds_coverage, get_pool_coverage};
+use crate::decisions::{record_decision, validate_rationale};
+use crate::governance::{effective_appeal_quorum, process_config_changes, queue_config_change};
+use crate::storage::*;
+use crate::types::*;
+use crate::{get_subaccount_balance, transfer_icrc1, TRANSFER_FEE};
+
+// Appeals the committee has not resolved within this period expire and refund their deposit.
+pub const APPEAL_VOTING_PERIOD: u64 = 14 * 24 * 60 * 60 * 1_000_000_000;
+
+#[ic_cdk::query]
+pub fn get_appeal_deposit() -> Nat {
+    APPEAL_DEPOSIT.with(|cell| cell.borrow().get().clone().0)
+}
+
+#[ic_cdk::query]
+pub fn get_appeal_quorum() -> u64 {
+    effective_appeal_quorum()
+}
+
+#[ic_cdk::query]
+pub fn get_appeal_deposit_subaccount(claim_id: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"appeal");
+    hasher.update(claim_id.to_be_bytes());
+    hasher.finalize().into()
+}
+
+#[ic_cdk::query]
+pub fn get_appeal(claim_id: u64) -> Option<Appeal> {
+    APPEALS.with(|appeals| appeals.borrow().get(&claim_id))
+}
+
+#[ic_cdk::query]
+pub fn is_appeal_committee_member(principal: Principal) -> bool {
+    APPEAL_COMMITTEE.with(|committee| committee.borrow().get(&principal).unwrap_or(false))
+}
+
+// Each claim can be appealed once, by its proposer, after an adverse decision.
+fn ensure_appealable(claim_id: u64, caller: Principal) -> Result<ClaimStatus, ClaimError> {
+    let claim = CLAIMS
+        .with(|claims| claims.borrow().get(&claim_id))
+        .ok_or(ClaimError::NotFound)?;
+
+    if claim.proposer != caller {
+        return Err(ClaimError::NotProposer);
+    }
+
+    if APPEALS.with(|appeals| appeals.borrow().contains_key(&claim_id)) {
+        return Err(ClaimError::AppealAlreadyOpened);
+    }
+
+    match claim.status {
+        ClaimStatus::Rejected
+        | ClaimStatus::Spam
+        | ClaimStatus::PartiallyApproved
+        | ClaimStatus::Disputed => Ok(claim.status),
+        _ => Err(ClaimError::NotAppealable),
+    }
+}
+
+#[ic_cdk::update]
+pub async fn open_appeal(claim_id: u64, rationale: String) -> Result<(), ClaimError> {
+    process_config_changes();
+
+    let caller = ic_cdk::api::caller();
+
+    validate_rationale(&rationale)?;
+    ensure_appealable(claim_id, caller)?;
+
+    let claim = CLAIMS
+        .with(|claims| claims.borrow().get(&claim_id))
+        .ok_or(ClaimError::NotFound)?;
+    let coverage_amount = match claim.coverage_id {
+        Some(coverage_id) => Some(
+            get_pool_coverage(claim.pool_canister_id, coverage_id)
+                .await?
+                .coverage_amount,
+        ),
+        None => None,
+    };
+
+    let required_deposit = APPEAL_DEPOSIT.with(|cell| cell.borrow().get().clone().0);
+
+    if required_deposit > Nat::from(0u64) {
+        let subaccount = get_appeal_deposit_subaccount(claim_id);
+        let balance = get_subaccount_balance(subaccount.to_vec()).await?;
+
+        if balance < required_deposit {
+            return Err(ClaimError::InsufficientDeposit);
+        }
+    }
+
+    // The claim may have changed while the balance was being fetched.
+    let previous_status = ensure_appealable(claim_id, caller)?;
+
+    // A rejected claim no longer holds its amount against the cover, which other claims may have
+    // taken since; the appeal only goes ahead while the full amount still fits.
+    if let Some(coverage_amount) = &coverage_amount {
+        let claim = CLAIMS
+            .with(|claims| claims.borrow().get(&claim_id))
+            .ok_or(ClaimError::NotFound)?;
+        if exceeds_coverage(&claim, &claim.amount, coverage_amount) {
+            return Err(ClaimError::ClaimExceedsCoverage);
+        }
+    }
+
+    CLAIMS.with(|claims| {
+        let mut claims_ref = claims.borrow_mut();
+        if let Some(mut claim) = claims_ref.get(&claim_id) {
+            claim.status = ClaimStatus::Appealed;
+            claims_ref.insert(claim_id, claim);
+        }
+    });
+
+    let appeal = Appeal {
+        claim_id,
+        previous_status,
+        status: AppealStatus::Open,
+        deposit_amount: required_deposit,
+        opened_at: ic_cdk::api::time(),
+        resolved_at: None,
+        uphold_votes: vec![],
+        dismiss_votes: vec![],
+        coverage_amount,
+    };
+
+    APPEALS.with(|appeals| {
+        appeals.borrow_mut().insert(claim_id, appeal);
+    });
+
+    record_decision(claim_id, caller, DecisionKind::Appeal, rationale);
+    Ok(())
+}
+
+fn is_overdue(appeal: &Appeal, current_time: u64) -> bool {
+    appeal.status == AppealStatus::Open && current_time >= appeal.opened_at + APPEAL_VOTING_PERIOD
+}
+
+// Lapses an overdue appeal, restoring the decision it challenged.
+pub(crate) fn expire_overdue_appeal(claim_id: u64) -> bool {
+    let current_time = ic_cdk::api::time();
+
+    let Some(mut appeal) = APPEALS
+        .with(|appeals| appeals.borrow().get(&claim_id))
+        .filter(|appeal| is_overdue(appeal, current_time))
+    else {
+        return false;
+    };
+
+    appeal.status = AppealStatus::Expired;
+    appeal.resolved_at = Some(current_time);
+
+    CLAIMS.with(|claims| {
+        let mut claims_ref = claims.borrow_mut();
+        if let Some(mut claim) = claims_ref.get(&claim_id) {
+            claim.status = appeal.previous_status.clone();
+            claims_ref.insert(claim_id, claim);
+        }
+    });
+
+    APPEALS.with(|appeals| {
+        appeals.borrow_mut().insert(claim_id, appeal);
+    });
+
+    true
+}
+
+#[ic_cdk::update]
+pub fn expire_appeal(claim_id: u64) -> Result<(), ClaimError> {
+    let appeal = APPEALS
+        .with(|appeals| appeals.borrow().get(&claim_id))
+        .filter(|appeal| appeal.status == AppealStatus::Open)
+        .ok_or(ClaimError::AppealNotOpen)?;
+
+    if !is_overdue(&appeal, ic_cdk::api::time()) {
+        return Err(ClaimError::AppealVotingPeriodNotExpired);
+    }
+
+    expire_overdue_appeal(claim_id);
+    Ok(())
+}
+
+fn appeal_ineligibility(claim: &Claim, voter: Principal) -> Option<IneligibilityReason> {
+    if claim.proposer == voter {
+        return Some(IneligibilityReason::Proposer);
+    }
+
+    if claim.receiver == voter || claim.covered_account == voter {
+        return Some(IneligibilityReason::Receiver);
+    }
+
+    let decision_ids = CLAIM_DECISIONS.with(|claim_decisions| {
+        claim_decisions
+            .borrow()
+            .get(&claim.id)
+            .map(|ids| ids.0)
+            .unwrap_or_default()
+    });
+
+    let took_original_decision = DECISIONS.with(|decisions| {
+        let decisions_ref = decisions.borrow();
+        decision_ids
+            .iter()
+            .filter_map(|decision_id| decisions_ref.get(decision_id))
+            .any(|decision| {
+                decision.actor == voter
+                    && matches!(
+                        decision.kind,
+                        DecisionKind::Approve | DecisionKind::Reject | DecisionKind::Spam
+                    )
+            })
+    });
+
+    if took_original_decision {
+        return Some(IneligibilityReason::OriginalDecisionMaker);
+    }
+
+    None
+}
+
+#[ic_cdk::update]
+pub fn vote_on_appeal(claim_id: u64, uphold: bool, rationale: String) -> Result<(), ClaimError> {
+    process_config_changes();
+
+    let caller = ic_cdk::api::caller();
+
+    if !is_appeal_committee_member(caller) {
+        return Err(ClaimError::NotAppealCommitteeMember);
+    }
+
+    validate_rationale(&rationale)?;
+
+    let mut claim = CLAIMS
+        .with(|claims| claims.borrow().get(&claim_id))
+        .ok_or(ClaimError::NotFound)?;
+
+    let mut appeal = APPEALS
+        .with(|appeals| appeals.borrow().get(&claim_id))
+        .filter(|appeal| appeal.status == AppealStatus::Open)
+        .ok_or(ClaimError::AppealNotOpen)?;
+
+    if is_overdue(&appeal, ic_cdk::api::time()) {
+        return Err(ClaimError::AppealExpired);
+    }
+
+    if let Some(reason) = appeal_ineligibility(&claim, caller) {
+        return Err(ClaimError::ApproverIneligible(reason));
+    }
+
+    if appeal.uphold_votes.contains(&caller) || appeal.dismiss_votes.contains(&caller) {
+        return Err(ClaimError::AlreadyVoted);
+    }
+
+    if uphold {
+        appeal.uphold_votes.push(caller);
+    } else {
+        appeal.dismiss_votes.push(caller);
+    }
+
+    let quorum = get_appeal_quorum() as usize;
+    let current_time = ic_cdk::api::time();
+
+    // Upholding approves the full amount, which has to fit next to the coverage's other claims.
+    if appeal.uphold_votes.len() >= quorum
+        && appeal
+            .coverage_amount
+            .as_ref()
+            .is_some_and(|coverage_amount| exceeds_coverage(&claim, &claim.amount, coverage_amount))
+    {
+        return Err(ClaimError::ClaimExceedsCoverage);
+    }
+
+    if appeal.uphold_votes.len() >= quorum {
+        // An upheld appeal approves the full requested amount.
+        appeal.status = AppealStatus::Upheld;
+        appeal.resolved_at = Some(current_time);
+
+        claim.status = ClaimStatus::Approved;
+        claim.approved_at = Some(current_time);
+        claim.approved_by = Some(caller);
+        claim.approved_amount = Some(claim.amount.clone());
+    } else if appeal.dismiss_votes.len() >= quorum {
+        // A dismissed appeal restores the original decision and forfeits the appeal deposit to the owner.
+        appeal.status = AppealStatus::Dismissed;
+        appeal.resolved_at = Some(current_time);
+
+        claim.status = appeal.previous_status.clone();
+    }
+
+    CLAIMS.with(|claims| {
+        claims.borrow_mut().insert(claim_id, claim);
+    });
+
+    APPEALS.with(|appeals| {
+        appeals.borrow_mut().insert(claim_id, appeal);
+    });
+
+    record_decision(
+        claim_id,
+        caller,
+        DecisionKind::AppealVote { uphold },
+        rationale,
+    );
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub async fn withdraw_appeal_deposit(claim_id: u64) -> Result<(), ClaimError> {
+    process_config_changes();
+    expire_overdue_appeal(claim_id);
+
+    let caller = ic_cdk::api::caller();
+
+    let (proposer, deposit_amount) = APPEALS.with(|appeals| {
+        let mut appeals_ref = appeals.borrow_mut();
+        let mut appeal = appeals_ref.get(&claim_id).ok_or(ClaimError::NotFound)?;
+
+        let proposer = CLAIMS
+            .with(|claims| claims.borrow().get(&claim_id))
+            .ok_or(ClaimError::NotFound)?
+            .proposer;
+
+        if proposer != caller {
+            return Err(ClaimError::NotProposer);
+        }
+
+        if appeal.status != AppealStatus::Upheld && appeal.status != AppealStatus::Expired {
+            return Err(ClaimError::AppealNotUpheld);
+        }
+
+        if appeal.deposit_amount == Nat::from(0u64) {
+            return Err(ClaimError::NoDepositToWithdraw);
+        }
+
+        let deposit_to_withdraw = appeal.deposit_amount.clone();
+        appeal.deposit_amount = Nat::from(0u64);
+        appeals_ref.insert(claim_id, appeal);
+
+        Ok((proposer, deposit_to_withdraw))
+    })?;
+
+    let subaccount = get_appeal_deposit_subaccount(claim_id);
+    transfer_icrc1(Some(subaccount.to_vec()), proposer, deposit_amount).await?;
+
+    Ok(())
+}
+
+// Deposits of dismissed appeals go to the owner.
+#[ic_cdk::update]
+pub async fn collect_forfeited_appeal_deposit(claim_id: u64) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    let deposit_amount = APPEALS.with(|appeals| {
+        let mut appeals_ref = appeals.borrow_mut();
+        let mut appeal = appeals_ref.get(&claim_id).ok_or(ClaimError::NotFound)?;
+
+        if appeal.status != AppealStatus::Dismissed {
+            return Err(ClaimError::AppealNotDismissed);
+        }
+
+        if appeal.deposit_amount == Nat::from(0u64) {
+            return Err(ClaimError::NoDepositToWithdraw);
+        }
+
+        let deposit_to_collect = appeal.deposit_amount.clone();
+        appeal.deposit_amount = Nat::from(0u64);
+        appeals_ref.insert(claim_id, appeal);
+
+        Ok(deposit_to_collect)
+    })?;
+
+    let subaccount = get_appeal_deposit_subaccount(claim_id);
+    transfer_icrc1(Some(subaccount.to_vec()), owner, deposit_amount).await?;
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn add_appeal_committee_member(member: Principal) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    APPEAL_COMMITTEE.with(|committee| {
+        committee.borrow_mut().insert(member, true);
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn remove_appeal_committee_member(member: Principal) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    APPEAL_COMMITTEE.with(|committee| {
+        committee.borrow_mut().remove(&member);
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn set_appeal_deposit(new_deposit: Nat) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    // A deposit that does not cover the transfer fee could never be refunded.
+    if new_deposit != Nat::from(0u64) && new_deposit <= TRANSFER_FEE.clone() {
+        return Err(ClaimError::InvalidConfig);
+    }
+
+    APPEAL_DEPOSIT.with(|cell| {
+        cell.borrow_mut().set(StorableNat(new_deposit)).ok();
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn set_appeal_quorum(new_quorum: u64) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    if new_quorum == 0 {
+        return Err(ClaimError::InvalidQuorum);
+    }
+
+    queue_config_change(ConfigChange::AppealQuorum(new_quorum))
+}
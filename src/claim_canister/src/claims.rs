@@ -2,26 +2,28 @@ use candid::{Nat, Principal};
 use ic_cdk::api::call::call;
 use sha2::{Digest, Sha256};
 
+use crate::appeals::expire_overdue_appeal;
+use crate::conflicts::ensure_no_conflict;
+use crate::decisions::{record_decision, validate_rationale};
+use crate::delegation::{get_payout_account, is_delegate};
+use crate::governance::{effective_execution_timeout, process_config_changes};
+use crate::parametric::{apply_reported_incidents, claim_execution_timeout, report_pool_incident};
+use crate::pools::*;
 use crate::storage::*;
 use crate::types::*;
 use crate::{get_subaccount_balance, transfer_icrc1};
 
-#[ic_cdk::query]
-pub fn get_claim_deposit() -> Nat {
-    CLAIM_DEPOSIT.with(|cell| cell.borrow().get().clone().0)
-}
-
 #[ic_cdk::query]
 pub fn get_claim_deposit_subaccount(
     user: Principal,
-    receiver: Principal,
+    coverage_id: u64,
     amount: Nat,
     pool_canister_id: Principal,
     description: String,
 ) -> [u8; 32] {
     let mut hasher = Sha256::new();
     hasher.update(user.as_slice());
-    hasher.update(receiver.as_slice());
+    hasher.update(coverage_id.to_be_bytes());
     hasher.update(&candid::encode_one(&amount).unwrap());
     hasher.update(pool_canister_id.as_slice());
     hasher.update(description.as_bytes());
@@ -30,7 +32,7 @@ pub fn get_claim_deposit_subaccount(
 
 #[ic_cdk::query]
 pub fn get_execution_timeout() -> u64 {
-    EXECUTION_TIMEOUT.with(|cell| cell.borrow().get().clone())
+    effective_execution_timeout()
 }
 
 #[ic_cdk::query]
@@ -48,25 +50,247 @@ pub fn get_claim(claim_id: u64) -> Option<ClaimInfo> {
             approved_at: claim.approved_at,
             approved_by: claim.approved_by,
             deposit_amount: claim.deposit_amount.clone(),
+            execution_started_at: claim.execution_started_at,
+            approved_amount: claim.approved_amount.clone(),
+            coverage_id: claim.coverage_id,
+            covered_account: claim.covered_account,
+            product_id: claim.product_id,
+            coverage_start: claim.coverage_start,
+            coverage_end: claim.coverage_end,
+            incident_id: claim.incident_id,
+            incident_time: claim.incident_time,
+            pool_incident_id: claim.pool_incident_id,
         })
     })
 }
 
+// Deposits of claims filed before claims were bound to coverages were keyed by receiver.
+fn legacy_claim_deposit_subaccount(claim: &Claim) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(claim.proposer.as_slice());
+    hasher.update(claim.receiver.as_slice());
+    hasher.update(&candid::encode_one(&claim.amount).unwrap());
+    hasher.update(claim.pool_canister_id.as_slice());
+    hasher.update(claim.description.as_bytes());
+    hasher.finalize().into()
+}
+
+// Undecided claims hold their requested amount against the cover, approved ones what was approved.
+fn committed_amount(claim: &Claim) -> Nat {
+    match claim.status {
+        ClaimStatus::Rejected | ClaimStatus::Spam => Nat::from(0u64),
+        ClaimStatus::Approved
+        | ClaimStatus::PartiallyApproved
+        | ClaimStatus::Executing
+        | ClaimStatus::Executed => claim
+            .approved_amount
+            .clone()
+            .unwrap_or_else(|| claim.amount.clone()),
+        ClaimStatus::Pending | ClaimStatus::Disputed | ClaimStatus::Appealed => {
+            claim.amount.clone()
+        }
+    }
+}
+
+fn committed_coverage_amount(
+    pool_canister_id: Principal,
+    coverage_id: u64,
+    excluded_claim_id: Option<u64>,
+) -> Nat {
+    let claim_ids = COVERAGE_CLAIMS.with(|coverage_claims| {
+        coverage_claims
+            .borrow()
+            .get(&(pool_canister_id, coverage_id))
+            .map(|ids| ids.0)
+            .unwrap_or_default()
+    });
+
+    CLAIMS.with(|claims| {
+        let claims_ref = claims.borrow();
+        claim_ids
+            .iter()
+            .filter(|claim_id| Some(**claim_id) != excluded_claim_id)
+            .filter_map(|claim_id| claims_ref.get(claim_id))
+            .fold(Nat::from(0u64), |total, claim| {
+                total + committed_amount(&claim)
+            })
+    })
+}
+
+// Whether `claimed` for this claim would take its coverage's claims past `coverage_amount`.
+pub(crate) fn exceeds_coverage(claim: &Claim, claimed: &Nat, coverage_amount: &Nat) -> bool {
+    let Some(coverage_id) = claim.coverage_id else {
+        return false;
+    };
+
+    let committed_by_others =
+        committed_coverage_amount(claim.pool_canister_id, coverage_id, Some(claim.id));
+    committed_by_others + claimed.clone() > *coverage_amount
+}
+
+fn index_coverage_claim(pool_canister_id: Principal, coverage_id: u64, claim_id: u64) {
+    COVERAGE_CLAIMS.with(|coverage_claims| {
+        let mut coverage_claims_ref = coverage_claims.borrow_mut();
+        let key = (pool_canister_id, coverage_id);
+        let mut ids = coverage_claims_ref.get(&key).unwrap_or(ClaimIds(vec![]));
+        if !ids.0.contains(&claim_id) {
+            ids.0.push(claim_id);
+            coverage_claims_ref.insert(key, ids);
+        }
+    });
+}
+
+// Rewrites claims stored in an older shape and indexes them by coverage.
+pub(crate) fn migrate_claims() {
+    let claims: Vec<Claim> =
+        CLAIMS.with(|claims| claims.borrow().iter().map(|(_, claim)| claim).collect());
+
+    for claim in claims {
+        if let Some(coverage_id) = claim.coverage_id {
+            index_coverage_claim(claim.pool_canister_id, coverage_id, claim.id);
+        }
+        CLAIMS.with(|claims| {
+            claims.borrow_mut().insert(claim.id, claim);
+        });
+    }
+}
+
+pub(crate) async fn get_pool_coverage(
+    pool_canister_id: Principal,
+    coverage_id: u64,
+) -> Result<Coverage, ClaimError> {
+    let coverage_result: Result<(Option<Coverage>,), _> =
+        call(pool_canister_id, "get_coverage", (coverage_id,)).await;
+
+    match coverage_result {
+        Ok((Some(coverage),)) => Ok(coverage),
+        Ok((None,)) => Err(ClaimError::CoverageNotFound),
+        Err(e) => Err(ClaimError::PoolCallFailed(format!("{:?}", e))),
+    }
+}
+
+async fn get_pool_incident(
+    pool_canister_id: Principal,
+    incident_id: u64,
+) -> Result<PoolIncident, ClaimError> {
+    let incident_result: Result<(Option<PoolIncident>,), _> =
+        call(pool_canister_id, "get_incident", (incident_id,)).await;
+
+    match incident_result {
+        Ok((Some(incident),)) => Ok(incident),
+        Ok((None,)) => Err(ClaimError::IncidentNotFound),
+        Err(e) => Err(ClaimError::PoolCallFailed(format!("{:?}", e))),
+    }
+}
+
+// The claim canister is the pool's executor, so the owner and the pool's approvers can record
+// incidents the pool manager has not reported and claims can still be filed for them.
+#[ic_cdk::update]
+pub async fn register_incident(
+    pool_canister_id: Principal,
+    product_id: u64,
+    incident_time: u64,
+    severity: IncidentSeverity,
+) -> Result<u64, ClaimError> {
+    process_config_changes();
+
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner && !is_pool_approver(pool_canister_id, caller) {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    get_registered_pool_config(pool_canister_id)?;
+
+    if incident_time > ic_cdk::api::time() {
+        return Err(ClaimError::InvalidIncidentTime);
+    }
+
+    report_pool_incident(pool_canister_id, product_id, incident_time, severity).await
+}
+
+// Cancelling a coverage ends its protection, so later incidents are no longer paid out.
+async fn ensure_coverage_in_force(claim: &Claim) -> Result<(), ClaimError> {
+    let Some(coverage_id) = claim.coverage_id else {
+        return Ok(());
+    };
+
+    let coverage = get_pool_coverage(claim.pool_canister_id, coverage_id).await?;
+
+    if coverage.status == CoverageStatus::Cancelled
+        && claim.incident_time >= coverage.end_time * 1_000_000_000
+    {
+        return Err(ClaimError::CoverageCancelled);
+    }
+
+    Ok(())
+}
+
+// Claims are filed for an incident recorded in the pool's registry, whose time is authoritative.
 #[ic_cdk::update]
 pub async fn add_claim(
-    receiver: Principal,
+    coverage_id: u64,
+    incident_id: u64,
     amount: Nat,
     pool_canister_id: Principal,
     description: String,
 ) -> Result<u64, ClaimError> {
+    process_config_changes();
+
     let caller = ic_cdk::api::caller();
 
-    let required_deposit = CLAIM_DEPOSIT.with(|cell| cell.borrow().get().clone().0);
+    get_registered_pool_config(pool_canister_id)?;
+
+    let coverage = get_pool_coverage(pool_canister_id, coverage_id).await?;
+    let covered_account = coverage.covered_account;
+
+    if caller != covered_account && !is_delegate(covered_account, caller) {
+        return Err(ClaimError::NotAuthorizedForCoverage);
+    }
+
+    if amount > coverage.coverage_amount {
+        return Err(ClaimError::ClaimExceedsCoverage);
+    }
+
+    let incident = get_pool_incident(pool_canister_id, incident_id).await?;
+
+    if incident.product_id != coverage.product_id {
+        return Err(ClaimError::IncidentNotForProduct);
+    }
+
+    let current_time = ic_cdk::api::time();
+    let incident_time = incident.incident_time * 1_000_000_000;
+
+    if incident_time > current_time {
+        return Err(ClaimError::InvalidIncidentTime);
+    }
+
+    // Claims stay possible during the product's grace period after the cover expired.
+    if current_time > coverage.claim_deadline * 1_000_000_000 {
+        return Err(ClaimError::ClaimDeadlinePassed);
+    }
+
+    // Cover bought after the incident happened cannot be claimed against it, and neither can
+    // incidents during the waiting period between purchase and the start of protection.
+    let purchased_at = coverage.purchased_at * 1_000_000_000;
+    let coverage_start = coverage.start_time * 1_000_000_000;
+    let coverage_end = coverage.end_time * 1_000_000_000;
+
+    if incident_time >= purchased_at && incident_time < coverage_start {
+        return Err(ClaimError::IncidentInWaitingPeriod);
+    }
+
+    if incident_time < coverage_start || incident_time >= coverage_end {
+        return Err(ClaimError::IncidentOutsideCoverage);
+    }
+
+    let required_deposit = claim_deposit_for(pool_canister_id, &amount);
 
     if required_deposit > Nat::from(0u64) {
         let subaccount = get_claim_deposit_subaccount(
             caller,
-            receiver,
+            coverage_id,
             amount.clone(),
             pool_canister_id,
             description.clone(),
@@ -78,6 +302,13 @@ pub async fn add_claim(
         }
     }
 
+    // Checked after the last await so that concurrent claims cannot together exceed the cover.
+    if committed_coverage_amount(pool_canister_id, coverage_id, None) + amount.clone()
+        > coverage.coverage_amount
+    {
+        return Err(ClaimError::ClaimExceedsCoverage);
+    }
+
     let claim_id = CLAIM_COUNTER.with(|counter| {
         let current = counter.borrow().get().clone();
         let new_counter = current + 1;
@@ -90,7 +321,7 @@ pub async fn add_claim(
     let claim = Claim {
         id: claim_id,
         proposer: caller,
-        receiver,
+        receiver: get_payout_account(covered_account),
         amount: amount.clone(),
         pool_canister_id,
         description,
@@ -99,32 +330,50 @@ pub async fn add_claim(
         approved_at: None,
         approved_by: None,
         deposit_amount: required_deposit,
+        execution_started_at: None,
+        approved_amount: None,
+        coverage_id: Some(coverage_id),
+        covered_account,
+        product_id: coverage.product_id,
+        coverage_start,
+        coverage_end,
+        incident_id: None,
+        incident_time,
+        pool_incident_id: Some(incident_id),
     };
 
     CLAIMS.with(|claims| {
         claims.borrow_mut().insert(claim_id, claim);
     });
+    index_coverage_claim(pool_canister_id, coverage_id, claim_id);
+
+    apply_reported_incidents(claim_id);
 
     Ok(claim_id)
 }
 
 #[ic_cdk::update]
-pub fn approve_claim(claim_id: u64) -> Result<(), ClaimError> {
-    let caller = ic_cdk::api::caller();
+pub async fn approve_claim(
+    claim_id: u64,
+    approved_amount: Option<Nat>,
+    rationale: String,
+) -> Result<(), ClaimError> {
+    process_config_changes();
 
-    let is_approver = APPROVERS.with(|approvers| approvers.borrow().get(&caller).unwrap_or(false));
+    let caller = ic_cdk::api::caller();
 
-    if !is_approver {
-        return Err(ClaimError::NotApprover);
-    }
+    validate_rationale(&rationale)?;
 
-    let approval_period = APPROVAL_PERIOD.with(|cell| cell.borrow().get().clone());
+    let claim = CLAIMS
+        .with(|claims| claims.borrow().get(&claim_id))
+        .ok_or(ClaimError::NotFound)?;
+    ensure_coverage_in_force(&claim).await?;
 
     CLAIMS.with(|claims| {
         let mut claims_ref = claims.borrow_mut();
         let mut claim = claims_ref.get(&claim_id).ok_or(ClaimError::NotFound)?;
 
-        if claim.status == ClaimStatus::Approved {
+        if claim.status == ClaimStatus::Approved || claim.status == ClaimStatus::PartiallyApproved {
             return Err(ClaimError::AlreadyApproved);
         }
 
@@ -132,22 +381,138 @@ pub fn approve_claim(claim_id: u64) -> Result<(), ClaimError> {
             return Err(ClaimError::AlreadyExecuted);
         }
 
+        if claim.status == ClaimStatus::Appealed {
+            return Err(ClaimError::AppealInProgress);
+        }
+
+        // Rejected, spam and disputed claims can only be revisited through an appeal.
+        if claim.status != ClaimStatus::Pending {
+            return Err(ClaimError::ClaimNotPending);
+        }
+
+        ensure_no_conflict(&claim, caller)?;
+
         let current_time = ic_cdk::api::time();
-        if current_time > claim.created_at + approval_period {
+        if current_time > claim.created_at + approval_period_for(claim.pool_canister_id) {
             return Err(ClaimError::ApprovalPeriodExpired);
         }
 
-        claim.status = ClaimStatus::Approved;
+        let approved_amount = approved_amount.unwrap_or_else(|| claim.amount.clone());
+        if approved_amount == Nat::from(0u64) || approved_amount > claim.amount {
+            return Err(ClaimError::InvalidApprovedAmount);
+        }
+
+        // A reduced payout only becomes executable once the proposer accepts it.
+        claim.status = if approved_amount < claim.amount {
+            ClaimStatus::PartiallyApproved
+        } else {
+            ClaimStatus::Approved
+        };
         claim.approved_at = Some(current_time);
         claim.approved_by = Some(caller);
+        claim.approved_amount = Some(approved_amount);
+
+        claims_ref.insert(claim_id, claim);
+        Ok(())
+    })?;
+
+    record_decision(claim_id, caller, DecisionKind::Approve, rationale);
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn reject_claim(claim_id: u64, rationale: String) -> Result<(), ClaimError> {
+    process_config_changes();
+
+    let caller = ic_cdk::api::caller();
+
+    validate_rationale(&rationale)?;
+
+    CLAIMS.with(|claims| {
+        let mut claims_ref = claims.borrow_mut();
+        let mut claim = claims_ref.get(&claim_id).ok_or(ClaimError::NotFound)?;
+
+        if claim.status != ClaimStatus::Pending {
+            return Err(ClaimError::ClaimNotPending);
+        }
+
+        ensure_no_conflict(&claim, caller)?;
+
+        claim.status = ClaimStatus::Rejected;
+        claims_ref.insert(claim_id, claim);
+        Ok(())
+    })?;
+
+    record_decision(claim_id, caller, DecisionKind::Reject, rationale);
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn accept_partial_approval(claim_id: u64) -> Result<(), ClaimError> {
+    process_config_changes();
+
+    let caller = ic_cdk::api::caller();
+
+    CLAIMS.with(|claims| {
+        let mut claims_ref = claims.borrow_mut();
+        let mut claim = claims_ref.get(&claim_id).ok_or(ClaimError::NotFound)?;
+
+        if claim.proposer != caller && claim.covered_account != caller {
+            return Err(ClaimError::NotProposer);
+        }
+
+        if claim.status != ClaimStatus::PartiallyApproved {
+            return Err(ClaimError::NotPartiallyApproved);
+        }
+
+        // The execution timelock starts from the proposer's acceptance.
+        claim.status = ClaimStatus::Approved;
+        claim.approved_at = Some(ic_cdk::api::time());
 
         claims_ref.insert(claim_id, claim);
         Ok(())
     })
 }
 
+#[ic_cdk::update]
+pub fn dispute_partial_approval(claim_id: u64, rationale: String) -> Result<(), ClaimError> {
+    process_config_changes();
+
+    let caller = ic_cdk::api::caller();
+
+    validate_rationale(&rationale)?;
+
+    CLAIMS.with(|claims| {
+        let mut claims_ref = claims.borrow_mut();
+        let mut claim = claims_ref.get(&claim_id).ok_or(ClaimError::NotFound)?;
+
+        if claim.proposer != caller && claim.covered_account != caller {
+            return Err(ClaimError::NotProposer);
+        }
+
+        if claim.status != ClaimStatus::PartiallyApproved {
+            return Err(ClaimError::NotPartiallyApproved);
+        }
+
+        claim.status = ClaimStatus::Disputed;
+
+        claims_ref.insert(claim_id, claim);
+        Ok(())
+    })?;
+
+    record_decision(claim_id, caller, DecisionKind::Dispute, rationale);
+    Ok(())
+}
+
 #[ic_cdk::update]
 pub async fn execute_claim(claim_id: u64) -> Result<(), ClaimError> {
+    process_config_changes();
+
+    let claim = CLAIMS
+        .with(|claims| claims.borrow().get(&claim_id))
+        .ok_or(ClaimError::NotFound)?;
+    ensure_coverage_in_force(&claim).await?;
+
     let (pool_canister_id, receiver, amount) = CLAIMS.with(
         |claims| -> Result<(Principal, Principal, Nat), ClaimError> {
             let mut claims_ref = claims.borrow_mut();
@@ -160,126 +525,153 @@ pub async fn execute_claim(claim_id: u64) -> Result<(), ClaimError> {
                 return Err(ClaimError::NotApproved);
             }
 
+            get_registered_pool_config(claim.pool_canister_id)?;
+
             let current_time = ic_cdk::api::time();
             let approved_time = claim.approved_at.ok_or(ClaimError::NotApproved)?;
-            let execution_timeout = EXECUTION_TIMEOUT.with(|cell| cell.borrow().get().clone());
+            let execution_timeout = claim_execution_timeout(&claim);
 
             if current_time < approved_time + execution_timeout {
                 return Err(ClaimError::ExecutionTimeoutNotExpired);
             }
 
             claim.status = ClaimStatus::Executing;
+            claim.execution_started_at = Some(current_time);
             let pool_canister_id = claim.pool_canister_id;
             let receiver = claim.receiver;
-            let amount = claim.amount.clone();
+            let amount = claim
+                .approved_amount
+                .clone()
+                .unwrap_or_else(|| claim.amount.clone());
 
             claims_ref.insert(claim_id, claim);
+            EXECUTING_CLAIMS.with(|executing| {
+                executing.borrow_mut().insert(claim_id, current_time);
+            });
             Ok((pool_canister_id, receiver, amount))
         },
     )?;
 
+    // The claim id doubles as the pool's idempotency key, so retrying a slash is always safe.
     let slash_result: Result<(Result<(), PoolError>,), _> =
-        call(pool_canister_id, "slash", (receiver, amount)).await;
+        call(pool_canister_id, "slash", (claim_id, receiver, amount)).await;
 
     let success = matches!(slash_result, Ok((Ok(()),)));
 
     if !success {
-        CLAIMS.with(|claims| {
-            let mut claims_ref = claims.borrow_mut();
-            if let Some(mut updated_claim) = claims_ref.get(&claim_id) {
-                updated_claim.status = ClaimStatus::Approved;
-                claims_ref.insert(claim_id, updated_claim);
-            }
-        });
-
+        set_claim_status(claim_id, ClaimStatus::Approved);
         return Err(ClaimError::PoolCallFailed(format!("{:?}", slash_result)));
     }
 
+    set_claim_status(claim_id, ClaimStatus::Executed);
+    Ok(())
+}
+
+pub(crate) fn set_claim_status(claim_id: u64, status: ClaimStatus) {
     CLAIMS.with(|claims| {
         let mut claims_ref = claims.borrow_mut();
         if let Some(mut updated_claim) = claims_ref.get(&claim_id) {
-            updated_claim.status = ClaimStatus::Executed;
+            if status == ClaimStatus::Approved {
+                updated_claim.execution_started_at = None;
+            }
+            if status != ClaimStatus::Executing {
+                EXECUTING_CLAIMS.with(|executing| {
+                    executing.borrow_mut().remove(&claim_id);
+                });
+            }
+            updated_claim.status = status;
             claims_ref.insert(claim_id, updated_claim);
         }
     });
-    Ok(())
 }
 
 #[ic_cdk::update]
 pub async fn withdraw_deposit(claim_id: u64) -> Result<(), ClaimError> {
-    let caller = ic_cdk::api::caller();
+    process_config_changes();
+    expire_overdue_appeal(claim_id);
 
-    let (proposer, receiver, amount, pool_canister_id, description, deposit_amount) =
-        CLAIMS.with(|claims| {
-            let mut claims_ref = claims.borrow_mut();
-            let mut claim = claims_ref.get(&claim_id).ok_or(ClaimError::NotFound)?;
+    let caller = ic_cdk::api::caller();
 
-            if claim.proposer != caller {
-                return Err(ClaimError::NotProposer);
-            }
+    let (claim, deposit_amount) = CLAIMS.with(|claims| {
+        let mut claims_ref = claims.borrow_mut();
+        let mut claim = claims_ref.get(&claim_id).ok_or(ClaimError::NotFound)?;
 
-            if claim.status == ClaimStatus::Pending
-                && claim.created_at + APPROVAL_PERIOD.with(|cell| cell.borrow().get().clone())
-                    > ic_cdk::api::time()
-            {
-                return Err(ClaimError::ApprovalPeriodNotExpired);
-            }
+        if claim.proposer != caller {
+            return Err(ClaimError::NotProposer);
+        }
 
-            if claim.status == ClaimStatus::Approved
-                && claim.approved_at.unwrap()
-                    + EXECUTION_TIMEOUT.with(|cell| cell.borrow().get().clone())
-                    > ic_cdk::api::time()
-            {
-                return Err(ClaimError::AlreadyApproved);
-            }
+        if claim.status == ClaimStatus::Pending
+            && claim.created_at + approval_period_for(claim.pool_canister_id) > ic_cdk::api::time()
+        {
+            return Err(ClaimError::ApprovalPeriodNotExpired);
+        }
 
-            if claim.status == ClaimStatus::Spam {
-                return Err(ClaimError::AlreadyMarkedAsSpam);
-            }
+        if (claim.status == ClaimStatus::Approved || claim.status == ClaimStatus::PartiallyApproved)
+            && claim.approved_at.unwrap() + claim_execution_timeout(&claim) > ic_cdk::api::time()
+        {
+            return Err(ClaimError::AlreadyApproved);
+        }
 
-            if claim.deposit_amount == Nat::from(0u64) {
-                return Err(ClaimError::NoDepositToWithdraw);
-            }
+        if claim.status == ClaimStatus::Spam {
+            return Err(ClaimError::AlreadyMarkedAsSpam);
+        }
 
-            let deposit_to_withdraw = claim.deposit_amount.clone();
-            claim.deposit_amount = Nat::from(0u64);
-            claims_ref.insert(claim_id, claim.clone());
+        if claim.status == ClaimStatus::Appealed {
+            return Err(ClaimError::AppealInProgress);
+        }
 
-            Ok((
-                claim.proposer,
-                claim.receiver,
-                claim.amount.clone(),
-                claim.pool_canister_id,
-                claim.description.clone(),
-                deposit_to_withdraw,
-            ))
-        })?;
+        if claim.deposit_amount == Nat::from(0u64) {
+            return Err(ClaimError::NoDepositToWithdraw);
+        }
 
-    let subaccount =
-        get_claim_deposit_subaccount(proposer, receiver, amount, pool_canister_id, description);
-    transfer_icrc1(Some(subaccount.to_vec()), proposer, deposit_amount).await?;
+        let deposit_to_withdraw = claim.deposit_amount.clone();
+        claim.deposit_amount = Nat::from(0u64);
+        claims_ref.insert(claim_id, claim.clone());
+
+        Ok((claim, deposit_to_withdraw))
+    })?;
+
+    let subaccount = match claim.coverage_id {
+        Some(coverage_id) => get_claim_deposit_subaccount(
+            claim.proposer,
+            coverage_id,
+            claim.amount.clone(),
+            claim.pool_canister_id,
+            claim.description.clone(),
+        ),
+        None => legacy_claim_deposit_subaccount(&claim),
+    };
+    transfer_icrc1(Some(subaccount.to_vec()), claim.proposer, deposit_amount).await?;
 
     Ok(())
 }
 
 #[ic_cdk::update]
-pub fn mark_as_spam(claim_id: u64) -> Result<(), ClaimError> {
-    let caller = ic_cdk::api::caller();
+pub fn mark_as_spam(claim_id: u64, rationale: String) -> Result<(), ClaimError> {
+    process_config_changes();
 
-    let is_approver = APPROVERS.with(|approvers| approvers.borrow().get(&caller).unwrap_or(false));
+    let caller = ic_cdk::api::caller();
 
-    if !is_approver {
-        return Err(ClaimError::NotApprover);
-    }
+    validate_rationale(&rationale)?;
 
     CLAIMS.with(|claims| {
         let mut claims_ref = claims.borrow_mut();
         let mut claim = claims_ref.get(&claim_id).ok_or(ClaimError::NotFound)?;
 
+        ensure_no_conflict(&claim, caller)?;
+
         if claim.status == ClaimStatus::Executed {
             return Err(ClaimError::AlreadyExecuted);
         }
 
+        if claim.status == ClaimStatus::Executing {
+            return Err(ClaimError::AlreadyExecuting);
+        }
+
+        if claim.status == ClaimStatus::Appealed {
+            return Err(ClaimError::AppealInProgress);
+        }
+
         if claim.status == ClaimStatus::Spam {
             return Err(ClaimError::AlreadyMarkedAsSpam);
         }
@@ -288,5 +680,8 @@ pub fn mark_as_spam(claim_id: u64) -> Result<(), ClaimError> {
         claims_ref.insert(claim_id, claim);
 
         Ok(())
-    })
+    })?;
+
+    record_decision(claim_id, caller, DecisionKind::Spam, rationale);
+    Ok(())
 }
@@ -0,0 +1,80 @@
+use candid::Principal;
+
+use crate::governance::{effective_conflict_rules, queue_config_change};
+use crate::pools::is_pool_approver;
+use crate::storage::*;
+use crate::types::*;
+
+// Longer cooling-off periods would lock new approvers out of every claim in flight.
+pub const MAX_COOLING_OFF_PERIOD: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+pub(crate) fn approver_ineligibility(
+    claim: &Claim,
+    approver: Principal,
+) -> Vec<IneligibilityReason> {
+    let mut reasons = vec![];
+
+    if !is_pool_approver(claim.pool_canister_id, approver) {
+        reasons.push(IneligibilityReason::NotApprover);
+    }
+
+    let rules = effective_conflict_rules();
+
+    if rules.exclude_proposer && claim.proposer == approver {
+        reasons.push(IneligibilityReason::Proposer);
+    }
+
+    if rules.exclude_receiver && (claim.receiver == approver || claim.covered_account == approver) {
+        reasons.push(IneligibilityReason::Receiver);
+    }
+
+    if let Some(added_at) = APPROVER_ADDED_AT.with(|added| added.borrow().get(&approver)) {
+        let eligible_at = added_at + rules.cooling_off_period;
+        if ic_cdk::api::time() < eligible_at {
+            reasons.push(IneligibilityReason::CoolingOff { eligible_at });
+        }
+    }
+
+    reasons
+}
+
+pub(crate) fn ensure_no_conflict(claim: &Claim, approver: Principal) -> Result<(), ClaimError> {
+    match approver_ineligibility(claim, approver).into_iter().next() {
+        Some(IneligibilityReason::NotApprover) => Err(ClaimError::NotApprover),
+        Some(reason) => Err(ClaimError::ApproverIneligible(reason)),
+        None => Ok(()),
+    }
+}
+
+#[ic_cdk::query]
+pub fn get_approver_ineligibility(
+    claim_id: u64,
+    approver: Principal,
+) -> Result<Vec<IneligibilityReason>, ClaimError> {
+    let claim = CLAIMS
+        .with(|claims| claims.borrow().get(&claim_id))
+        .ok_or(ClaimError::NotFound)?;
+
+    Ok(approver_ineligibility(&claim, approver))
+}
+
+#[ic_cdk::query]
+pub fn get_conflict_rules() -> ConflictRules {
+    effective_conflict_rules()
+}
+
+#[ic_cdk::update]
+pub fn set_conflict_rules(rules: ConflictRules) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    if rules.cooling_off_period > MAX_COOLING_OFF_PERIOD {
+        return Err(ClaimError::InvalidConfig);
+    }
+
+    queue_config_change(ConfigChange::ConflictRules(rules))
+}
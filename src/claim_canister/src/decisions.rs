@@ -0,0 +1,95 @@
+use candid::Principal;
+
+use crate::storage::*;
+use crate::types::*;
+
+const MAX_RATIONALE_LENGTH: usize = 2_000;
+
+pub(crate) fn validate_rationale(rationale: &str) -> Result<(), ClaimError> {
+    if rationale.trim().is_empty() || rationale.len() > MAX_RATIONALE_LENGTH {
+        return Err(ClaimError::InvalidRationale);
+    }
+    Ok(())
+}
+
+// The audit log is append-only: records are never modified or removed.
+pub(crate) fn record_decision(
+    claim_id: u64,
+    actor: Principal,
+    kind: DecisionKind,
+    rationale: String,
+) {
+    let decision_id = DECISION_COUNTER.with(|counter| {
+        let current = counter.borrow().get().clone();
+        let new_counter = current + 1;
+        counter.borrow_mut().set(new_counter).ok();
+        current
+    });
+
+    let record = DecisionRecord {
+        id: decision_id,
+        claim_id,
+        actor,
+        kind,
+        rationale,
+        decided_at: ic_cdk::api::time(),
+    };
+
+    DECISIONS.with(|decisions| {
+        decisions.borrow_mut().insert(decision_id, record);
+    });
+
+    CLAIM_DECISIONS.with(|claim_decisions| {
+        let mut claim_decisions_ref = claim_decisions.borrow_mut();
+        let mut decision_ids = claim_decisions_ref
+            .get(&claim_id)
+            .unwrap_or(DecisionIds(vec![]));
+        decision_ids.0.push(decision_id);
+        claim_decisions_ref.insert(claim_id, decision_ids);
+    });
+
+    APPROVER_DECISIONS.with(|approver_decisions| {
+        let mut approver_decisions_ref = approver_decisions.borrow_mut();
+        let mut decision_ids = approver_decisions_ref
+            .get(&actor)
+            .unwrap_or(DecisionIds(vec![]));
+        decision_ids.0.push(decision_id);
+        approver_decisions_ref.insert(actor, decision_ids);
+    });
+}
+
+fn get_decisions(decision_ids: Vec<u64>) -> Vec<DecisionRecord> {
+    DECISIONS.with(|decisions| {
+        let decisions_ref = decisions.borrow();
+        decision_ids
+            .iter()
+            .filter_map(|decision_id| decisions_ref.get(decision_id))
+            .collect()
+    })
+}
+
+#[ic_cdk::query]
+pub fn get_claim_decisions(claim_id: u64) -> Vec<DecisionRecord> {
+    let decision_ids = CLAIM_DECISIONS.with(|claim_decisions| {
+        claim_decisions
+            .borrow()
+            .get(&claim_id)
+            .map(|ids| ids.0)
+            .unwrap_or_default()
+    });
+
+    get_decisions(decision_ids)
+}
+
+#[ic_cdk::query]
+pub fn get_approver_decisions(approver: Principal) -> Vec<DecisionRecord> {
+    let decision_ids = APPROVER_DECISIONS.with(|approver_decisions| {
+        approver_decisions
+            .borrow()
+            .get(&approver)
+            .map(|ids| ids.0)
+            .unwrap_or_default()
+    });
+
+    get_decisions(decision_ids)
+}
@@ -0,0 +1,90 @@
+use candid::Principal;
+
+use crate::storage::*;
+use crate::types::*;
+
+const MAX_DELEGATES_PER_ACCOUNT: usize = 20;
+
+pub(crate) fn is_delegate(covered_account: Principal, principal: Principal) -> bool {
+    DELEGATES.with(|delegates| {
+        delegates
+            .borrow()
+            .get(&covered_account)
+            .is_some_and(|account_delegates| account_delegates.0.contains(&principal))
+    })
+}
+
+#[ic_cdk::query]
+pub fn get_delegates(covered_account: Principal) -> Vec<Principal> {
+    DELEGATES.with(|delegates| {
+        delegates
+            .borrow()
+            .get(&covered_account)
+            .map(|account_delegates| account_delegates.0)
+            .unwrap_or_default()
+    })
+}
+
+// Payouts for a covered account go to the account itself unless it designated another one.
+#[ic_cdk::query]
+pub fn get_payout_account(covered_account: Principal) -> Principal {
+    PAYOUT_ACCOUNTS
+        .with(|accounts| accounts.borrow().get(&covered_account))
+        .unwrap_or(covered_account)
+}
+
+#[ic_cdk::update]
+pub fn add_delegate(delegate: Principal) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+
+    DELEGATES.with(|delegates| {
+        let mut delegates_ref = delegates.borrow_mut();
+        let mut account_delegates = delegates_ref.get(&caller).unwrap_or(Delegates(vec![]));
+
+        if account_delegates.0.contains(&delegate) {
+            return Ok(());
+        }
+
+        if account_delegates.0.len() >= MAX_DELEGATES_PER_ACCOUNT {
+            return Err(ClaimError::DelegateLimitReached);
+        }
+
+        account_delegates.0.push(delegate);
+        delegates_ref.insert(caller, account_delegates);
+        Ok(())
+    })
+}
+
+#[ic_cdk::update]
+pub fn remove_delegate(delegate: Principal) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+
+    DELEGATES.with(|delegates| {
+        let mut delegates_ref = delegates.borrow_mut();
+        if let Some(mut account_delegates) = delegates_ref.get(&caller) {
+            account_delegates.0.retain(|existing| *existing != delegate);
+            delegates_ref.insert(caller, account_delegates);
+        }
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn set_payout_account(payout_account: Option<Principal>) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+
+    match payout_account {
+        Some(account) if account == Principal::anonymous() => {
+            return Err(ClaimError::InvalidPayoutAccount);
+        }
+        Some(account) => PAYOUT_ACCOUNTS.with(|accounts| {
+            accounts.borrow_mut().insert(caller, account);
+        }),
+        None => PAYOUT_ACCOUNTS.with(|accounts| {
+            accounts.borrow_mut().remove(&caller);
+        }),
+    }
+
+    Ok(())
+}
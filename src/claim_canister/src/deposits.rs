@@ -0,0 +1,91 @@
+use candid::{Nat, Principal};
+
+use crate::pools::claim_deposit_for;
+use crate::storage::*;
+use crate::types::*;
+
+const BASIS_POINTS: u64 = 10_000;
+
+pub(crate) fn validate_deposit_schedule(schedule: &DepositSchedule) -> Result<(), ClaimError> {
+    if schedule.bps > BASIS_POINTS || schedule.max < schedule.min {
+        return Err(ClaimError::InvalidDepositSchedule);
+    }
+
+    // A zero cap would silently turn a nonzero base or rate into no deposit at all.
+    let zero = Nat::from(0u64);
+    if schedule.max == zero && (schedule.base > zero || schedule.bps > 0) {
+        return Err(ClaimError::InvalidDepositSchedule);
+    }
+    Ok(())
+}
+
+// Canisters installed before deposit schedules kept a single flat deposit.
+pub(crate) fn migrate_legacy_claim_deposit() {
+    let zero = Nat::from(0u64);
+    let legacy_deposit = LEGACY_CLAIM_DEPOSIT.with(|cell| cell.borrow().get().clone().0);
+    if legacy_deposit == zero {
+        return;
+    }
+
+    let schedule = DEPOSIT_SCHEDULE.with(|cell| cell.borrow().get().clone());
+    let unset =
+        schedule.base == zero && schedule.bps == 0 && schedule.min == zero && schedule.max == zero;
+
+    if unset {
+        DEPOSIT_SCHEDULE.with(|cell| {
+            cell.borrow_mut()
+                .set(DepositSchedule {
+                    base: legacy_deposit.clone(),
+                    bps: 0,
+                    min: legacy_deposit.clone(),
+                    max: legacy_deposit,
+                })
+                .ok();
+        });
+    }
+
+    LEGACY_CLAIM_DEPOSIT.with(|cell| {
+        cell.borrow_mut().set(StorableNat(zero)).ok();
+    });
+}
+
+pub(crate) fn compute_deposit(schedule: &DepositSchedule, amount: &Nat) -> Nat {
+    let proportional = amount.clone() * schedule.bps / BASIS_POINTS;
+    let deposit = schedule.base.clone() + proportional;
+
+    if deposit < schedule.min {
+        schedule.min.clone()
+    } else if deposit > schedule.max {
+        schedule.max.clone()
+    } else {
+        deposit
+    }
+}
+
+#[ic_cdk::query]
+pub fn get_claim_deposit(pool_canister_id: Principal, amount: Nat) -> Nat {
+    claim_deposit_for(pool_canister_id, &amount)
+}
+
+#[ic_cdk::query]
+pub fn get_deposit_schedule() -> DepositSchedule {
+    DEPOSIT_SCHEDULE.with(|cell| cell.borrow().get().clone())
+}
+
+#[ic_cdk::update]
+pub fn set_deposit_schedule(schedule: DepositSchedule) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    validate_deposit_schedule(&schedule)?;
+
+    DEPOSIT_SCHEDULE.with(|cell| {
+        cell.borrow_mut().set(schedule).ok();
+    });
+
+    Ok(())
+}
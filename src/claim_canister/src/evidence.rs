@@ -0,0 +1,120 @@
+use candid::Principal;
+
+use crate::pools::is_pool_approver;
+use crate::storage::*;
+use crate::types::*;
+
+// Limits keep per-claim evidence and discussion from growing stable memory without bound.
+const CONTENT_HASH_LENGTH: usize = 32;
+const MAX_MIME_TYPE_LENGTH: usize = 128;
+const MAX_URI_LENGTH: usize = 512;
+const MAX_EVIDENCE_PER_CLAIM: usize = 20;
+const MAX_COMMENT_LENGTH: usize = 2_000;
+const MAX_COMMENTS_PER_CLAIM: usize = 100;
+
+fn ensure_can_contribute(claim_id: u64, caller: Principal) -> Result<(), ClaimError> {
+    let claim = CLAIMS
+        .with(|claims| claims.borrow().get(&claim_id))
+        .ok_or(ClaimError::NotFound)?;
+
+    if claim.proposer != caller
+        && claim.covered_account != caller
+        && !is_pool_approver(claim.pool_canister_id, caller)
+    {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    if claim.status != ClaimStatus::Pending {
+        return Err(ClaimError::ClaimNotPending);
+    }
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn add_claim_evidence(
+    claim_id: u64,
+    content_hash: Vec<u8>,
+    mime_type: String,
+    uri: String,
+) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    ensure_can_contribute(claim_id, caller)?;
+
+    if content_hash.len() != CONTENT_HASH_LENGTH
+        || mime_type.is_empty()
+        || mime_type.len() > MAX_MIME_TYPE_LENGTH
+        || uri.is_empty()
+        || uri.len() > MAX_URI_LENGTH
+    {
+        return Err(ClaimError::InvalidEvidence);
+    }
+
+    CLAIM_EVIDENCE.with(|evidence| {
+        let mut evidence_ref = evidence.borrow_mut();
+        let mut claim_evidence = evidence_ref.get(&claim_id).unwrap_or(ClaimEvidence(vec![]));
+
+        if claim_evidence.0.len() >= MAX_EVIDENCE_PER_CLAIM {
+            return Err(ClaimError::EvidenceLimitReached);
+        }
+
+        claim_evidence.0.push(EvidenceItem {
+            content_hash,
+            mime_type,
+            uri,
+            submitter: caller,
+            submitted_at: ic_cdk::api::time(),
+        });
+        evidence_ref.insert(claim_id, claim_evidence);
+        Ok(())
+    })
+}
+
+#[ic_cdk::update]
+pub fn add_claim_comment(claim_id: u64, text: String) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    ensure_can_contribute(claim_id, caller)?;
+
+    if text.trim().is_empty() || text.len() > MAX_COMMENT_LENGTH {
+        return Err(ClaimError::InvalidComment);
+    }
+
+    CLAIM_COMMENTS.with(|comments| {
+        let mut comments_ref = comments.borrow_mut();
+        let mut claim_comments = comments_ref.get(&claim_id).unwrap_or(ClaimComments(vec![]));
+
+        if claim_comments.0.len() >= MAX_COMMENTS_PER_CLAIM {
+            return Err(ClaimError::CommentLimitReached);
+        }
+
+        claim_comments.0.push(ClaimComment {
+            author: caller,
+            text,
+            created_at: ic_cdk::api::time(),
+        });
+        comments_ref.insert(claim_id, claim_comments);
+        Ok(())
+    })
+}
+
+#[ic_cdk::query]
+pub fn get_claim_evidence(claim_id: u64) -> Vec<EvidenceItem> {
+    CLAIM_EVIDENCE.with(|evidence| {
+        evidence
+            .borrow()
+            .get(&claim_id)
+            .map(|claim_evidence| claim_evidence.0)
+            .unwrap_or_default()
+    })
+}
+
+#[ic_cdk::query]
+pub fn get_claim_comments(claim_id: u64) -> Vec<ClaimComment> {
+    CLAIM_COMMENTS.with(|comments| {
+        comments
+            .borrow()
+            .get(&claim_id)
+            .map(|claim_comments| claim_comments.0)
+            .unwrap_or_default()
+    })
+}
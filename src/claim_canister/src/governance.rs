@@ -1,8 +1,306 @@
-use candid::{Nat, Principal};
+use candid::Principal;
 
+use crate::conflicts::MAX_COOLING_OFF_PERIOD;
+use crate::deposits::validate_deposit_schedule;
+use crate::pools::forget_approver_if_unused;
 use crate::storage::*;
 use crate::types::*;
 
+// Changes that affect claims already in flight only take effect after this delay.
+pub const CONFIG_CHANGE_DELAY: u64 = 2 * 24 * 60 * 60 * 1_000_000_000;
+
+pub(crate) fn process_config_changes() {
+    let current_time = ic_cdk::api::time();
+
+    let pending = PENDING_CONFIG_CHANGES.with(|cell| cell.borrow().get().clone());
+    let (due, remaining): (Vec<_>, Vec<_>) = pending
+        .0
+        .into_iter()
+        .partition(|pending| pending.effective_at <= current_time);
+
+    if due.is_empty() {
+        return;
+    }
+
+    for pending in due {
+        match pending.change {
+            ConfigChange::ApprovalPeriod(period) => APPROVAL_PERIOD.with(|cell| {
+                cell.borrow_mut().set(period).ok();
+            }),
+            ConfigChange::ExecutionTimeout(timeout) => EXECUTION_TIMEOUT.with(|cell| {
+                cell.borrow_mut().set(timeout).ok();
+            }),
+            ConfigChange::LedgerCanisterId(ledger_id) => LEDGER_CANISTER_ID.with(|cell| {
+                cell.borrow_mut().set(ledger_id).ok();
+            }),
+            ConfigChange::ConflictRules(rules) => CONFLICT_RULES.with(|cell| {
+                cell.borrow_mut().set(rules).ok();
+            }),
+            ConfigChange::AppealQuorum(quorum) => APPEAL_QUORUM.with(|cell| {
+                cell.borrow_mut().set(quorum).ok();
+            }),
+            change => {
+                // Changes for pools that were unregistered meanwhile are dropped.
+                let pool = changed_pool(&change).and_then(|pool_canister_id| {
+                    POOLS.with(|pools| pools.borrow().get(&pool_canister_id))
+                });
+                if let Some(pool) = pool {
+                    let previous_approvers = pool.approvers.clone();
+                    let pool = apply_pool_change(pool, &change);
+                    POOLS.with(|pools| {
+                        pools
+                            .borrow_mut()
+                            .insert(pool.pool_canister_id, pool.clone());
+                    });
+                    for approver in previous_approvers {
+                        if !pool.approvers.contains(&approver) {
+                            forget_approver_if_unused(approver);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    PENDING_CONFIG_CHANGES.with(|cell| {
+        cell.borrow_mut().set(PendingConfigChanges(remaining)).ok();
+    });
+}
+
+fn changed_pool(change: &ConfigChange) -> Option<Principal> {
+    match change {
+        ConfigChange::PoolApprovalPeriod {
+            pool_canister_id, ..
+        }
+        | ConfigChange::PoolExecutionTimeout {
+            pool_canister_id, ..
+        }
+        | ConfigChange::PoolApprovers {
+            pool_canister_id, ..
+        }
+        | ConfigChange::PoolDepositSchedule {
+            pool_canister_id, ..
+        } => Some(*pool_canister_id),
+        _ => None,
+    }
+}
+
+fn apply_pool_change(mut pool: RegisteredPool, change: &ConfigChange) -> RegisteredPool {
+    match change {
+        ConfigChange::PoolApprovalPeriod {
+            approval_period, ..
+        } => pool.approval_period = *approval_period,
+        ConfigChange::PoolExecutionTimeout {
+            execution_timeout, ..
+        } => pool.execution_timeout = *execution_timeout,
+        ConfigChange::PoolApprovers { approvers, .. } => pool.approvers = approvers.clone(),
+        ConfigChange::PoolDepositSchedule {
+            deposit_schedule, ..
+        } => pool.deposit_schedule = deposit_schedule.clone(),
+        _ => {}
+    }
+    pool
+}
+
+// Queries cannot apply due changes, so reads resolve them on the fly.
+fn due_changes() -> Vec<ConfigChange> {
+    let current_time = ic_cdk::api::time();
+
+    PENDING_CONFIG_CHANGES.with(|cell| {
+        cell.borrow()
+            .get()
+            .0
+            .iter()
+            .filter(|pending| pending.effective_at <= current_time)
+            .map(|pending| pending.change.clone())
+            .collect()
+    })
+}
+
+pub(crate) fn effective_approval_period() -> u64 {
+    due_changes()
+        .into_iter()
+        .find_map(|change| match change {
+            ConfigChange::ApprovalPeriod(period) => Some(period),
+            _ => None,
+        })
+        .unwrap_or_else(|| APPROVAL_PERIOD.with(|cell| cell.borrow().get().clone()))
+}
+
+pub(crate) fn effective_execution_timeout() -> u64 {
+    due_changes()
+        .into_iter()
+        .find_map(|change| match change {
+            ConfigChange::ExecutionTimeout(timeout) => Some(timeout),
+            _ => None,
+        })
+        .unwrap_or_else(|| EXECUTION_TIMEOUT.with(|cell| cell.borrow().get().clone()))
+}
+
+pub(crate) fn effective_ledger_canister_id() -> Principal {
+    due_changes()
+        .into_iter()
+        .find_map(|change| match change {
+            ConfigChange::LedgerCanisterId(ledger_id) => Some(ledger_id),
+            _ => None,
+        })
+        .unwrap_or_else(|| LEDGER_CANISTER_ID.with(|cell| cell.borrow().get().clone()))
+}
+
+pub(crate) fn effective_conflict_rules() -> ConflictRules {
+    due_changes()
+        .into_iter()
+        .find_map(|change| match change {
+            ConfigChange::ConflictRules(rules) => Some(rules),
+            _ => None,
+        })
+        .unwrap_or_else(|| CONFLICT_RULES.with(|cell| cell.borrow().get().clone()))
+}
+
+pub(crate) fn effective_appeal_quorum() -> u64 {
+    due_changes()
+        .into_iter()
+        .find_map(|change| match change {
+            ConfigChange::AppealQuorum(quorum) => Some(quorum),
+            _ => None,
+        })
+        .unwrap_or_else(|| APPEAL_QUORUM.with(|cell| cell.borrow().get().clone()))
+}
+
+// Approvers named in a queued change, which keep their cooling-off timestamp until it applies.
+pub(crate) fn pending_pool_approvers() -> Vec<Principal> {
+    PENDING_CONFIG_CHANGES.with(|cell| {
+        cell.borrow()
+            .get()
+            .0
+            .iter()
+            .flat_map(|pending| match &pending.change {
+                ConfigChange::PoolApprovers { approvers, .. } => approvers.clone(),
+                _ => vec![],
+            })
+            .collect()
+    })
+}
+
+pub(crate) fn effective_pool(pool_canister_id: Principal) -> Option<RegisteredPool> {
+    let pool = POOLS.with(|pools| pools.borrow().get(&pool_canister_id))?;
+
+    Some(
+        due_changes()
+            .iter()
+            .filter(|change| changed_pool(change) == Some(pool_canister_id))
+            .fold(pool, apply_pool_change),
+    )
+}
+
+pub(crate) fn cancel_pool_config_changes(pool_canister_id: Principal) {
+    let cancelled_approvers = PENDING_CONFIG_CHANGES.with(|cell| {
+        let mut pending = cell.borrow().get().clone();
+        let (cancelled, remaining): (Vec<_>, Vec<_>) = pending
+            .0
+            .into_iter()
+            .partition(|pending| changed_pool(&pending.change) == Some(pool_canister_id));
+        pending.0 = remaining;
+        cell.borrow_mut().set(pending).ok();
+        cancelled
+            .into_iter()
+            .flat_map(|pending| match pending.change {
+                ConfigChange::PoolApprovers { approvers, .. } => approvers,
+                _ => vec![],
+            })
+            .collect::<Vec<_>>()
+    });
+
+    for approver in cancelled_approvers {
+        forget_approver_if_unused(approver);
+    }
+}
+
+// A newer change to the same parameter replaces the pending one.
+fn schedule_config_change(change: ConfigChange) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    queue_config_change(change)
+}
+
+pub(crate) fn queue_config_change(change: ConfigChange) -> Result<(), ClaimError> {
+    let is_valid = match change {
+        ConfigChange::ApprovalPeriod(period) => period > 0,
+        ConfigChange::ExecutionTimeout(timeout) => timeout > 0,
+        ConfigChange::LedgerCanisterId(ledger_id) => ledger_id != Principal::anonymous(),
+        ConfigChange::PoolApprovalPeriod {
+            approval_period, ..
+        } => approval_period != Some(0),
+        ConfigChange::PoolExecutionTimeout {
+            execution_timeout, ..
+        } => execution_timeout != Some(0),
+        ConfigChange::ConflictRules(ref rules) => {
+            rules.cooling_off_period <= MAX_COOLING_OFF_PERIOD
+        }
+        ConfigChange::AppealQuorum(quorum) => quorum > 0,
+        ConfigChange::PoolApprovers { .. } => true,
+        ConfigChange::PoolDepositSchedule {
+            ref deposit_schedule,
+            ..
+        } => deposit_schedule
+            .as_ref()
+            .is_none_or(|schedule| validate_deposit_schedule(schedule).is_ok()),
+    };
+
+    if !is_valid {
+        return Err(ClaimError::InvalidConfig);
+    }
+
+    process_config_changes();
+
+    PENDING_CONFIG_CHANGES.with(|cell| {
+        let mut pending = cell.borrow().get().clone();
+        pending.0.retain(|pending| {
+            std::mem::discriminant(&pending.change) != std::mem::discriminant(&change)
+                || changed_pool(&pending.change) != changed_pool(&change)
+        });
+        pending.0.push(PendingConfigChange {
+            change,
+            effective_at: ic_cdk::api::time() + CONFIG_CHANGE_DELAY,
+        });
+        cell.borrow_mut().set(pending).ok();
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::query]
+pub fn get_config() -> ClaimConfig {
+    let current_time = ic_cdk::api::time();
+    let pending_owner = PENDING_OWNER.with(|cell| cell.borrow().get().clone());
+
+    ClaimConfig {
+        owner: OWNER.with(|cell| cell.borrow().get().clone()),
+        pending_owner: (pending_owner != Principal::anonymous()).then_some(pending_owner),
+        ledger_canister_id: effective_ledger_canister_id(),
+        approval_period: effective_approval_period(),
+        execution_timeout: effective_execution_timeout(),
+        deposit_schedule: DEPOSIT_SCHEDULE.with(|cell| cell.borrow().get().clone()),
+        conflict_rules: effective_conflict_rules(),
+        appeal_deposit: APPEAL_DEPOSIT.with(|cell| cell.borrow().get().clone().0),
+        appeal_quorum: effective_appeal_quorum(),
+        pending_changes: PENDING_CONFIG_CHANGES.with(|cell| {
+            cell.borrow()
+                .get()
+                .0
+                .iter()
+                .filter(|pending| pending.effective_at > current_time)
+                .cloned()
+                .collect()
+        }),
+    }
+}
+
 #[ic_cdk::query]
 pub fn is_approver(principal: Principal) -> bool {
     APPROVERS.with(|approvers| approvers.borrow().get(&principal).unwrap_or(false))
@@ -14,7 +312,7 @@ pub fn get_owner() -> Principal {
 }
 
 #[ic_cdk::update]
-pub fn set_claim_deposit(new_deposit: Nat) -> Result<(), ClaimError> {
+pub fn propose_owner(new_owner: Principal) -> Result<(), ClaimError> {
     let caller = ic_cdk::api::caller();
     let owner = OWNER.with(|cell| cell.borrow().get().clone());
 
@@ -22,29 +320,49 @@ pub fn set_claim_deposit(new_deposit: Nat) -> Result<(), ClaimError> {
         return Err(ClaimError::InsufficientPermissions);
     }
 
-    CLAIM_DEPOSIT.with(|cell| {
-        cell.borrow_mut().set(StorableNat(new_deposit)).ok();
+    // Proposing the anonymous principal cancels a pending transfer.
+    PENDING_OWNER.with(|cell| {
+        cell.borrow_mut().set(new_owner).ok();
     });
 
     Ok(())
 }
 
 #[ic_cdk::update]
-pub fn set_execution_timeout(new_timeout: u64) -> Result<(), ClaimError> {
+pub fn accept_ownership() -> Result<(), ClaimError> {
     let caller = ic_cdk::api::caller();
-    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+    let pending_owner = PENDING_OWNER.with(|cell| cell.borrow().get().clone());
 
-    if caller != owner {
-        return Err(ClaimError::InsufficientPermissions);
+    if caller == Principal::anonymous() || caller != pending_owner {
+        return Err(ClaimError::NotPendingOwner);
     }
 
-    EXECUTION_TIMEOUT.with(|cell| {
-        cell.borrow_mut().set(new_timeout).ok();
+    OWNER.with(|cell| {
+        cell.borrow_mut().set(caller).ok();
+    });
+
+    PENDING_OWNER.with(|cell| {
+        cell.borrow_mut().set(Principal::anonymous()).ok();
     });
 
     Ok(())
 }
 
+#[ic_cdk::update]
+pub fn set_approval_period(new_period: u64) -> Result<(), ClaimError> {
+    schedule_config_change(ConfigChange::ApprovalPeriod(new_period))
+}
+
+#[ic_cdk::update]
+pub fn set_execution_timeout(new_timeout: u64) -> Result<(), ClaimError> {
+    schedule_config_change(ConfigChange::ExecutionTimeout(new_timeout))
+}
+
+#[ic_cdk::update]
+pub fn set_ledger_canister_id(new_ledger_id: Principal) -> Result<(), ClaimError> {
+    schedule_config_change(ConfigChange::LedgerCanisterId(new_ledger_id))
+}
+
 #[ic_cdk::update]
 pub fn add_approver(approver: Principal) -> Result<(), ClaimError> {
     let caller = ic_cdk::api::caller();
@@ -58,6 +376,10 @@ pub fn add_approver(approver: Principal) -> Result<(), ClaimError> {
         approvers.borrow_mut().insert(approver, true);
     });
 
+    APPROVER_ADDED_AT.with(|added| {
+        added.borrow_mut().insert(approver, ic_cdk::api::time());
+    });
+
     Ok(())
 }
 
@@ -74,5 +396,7 @@ pub fn remove_approver(approver: Principal) -> Result<(), ClaimError> {
         approvers.borrow_mut().remove(&approver);
     });
 
+    forget_approver_if_unused(approver);
+
     Ok(())
 }
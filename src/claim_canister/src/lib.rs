@@ -1,11 +1,24 @@
 use candid::{Nat, Principal};
 use ic_cdk::api::call::call;
 
+pub mod appeals;
 pub mod claims;
+pub mod conflicts;
+pub mod decisions;
+pub mod delegation;
+pub mod deposits;
+pub mod evidence;
 pub mod governance;
+pub mod parametric;
+pub mod pools;
+pub mod reconciliation;
 pub mod storage;
 pub mod types;
 
+use claims::migrate_claims;
+use deposits::migrate_legacy_claim_deposit;
+use governance::effective_ledger_canister_id;
+use reconciliation::{index_executing_claims, setup_reconciliation_timer};
 use storage::*;
 use types::*;
 
@@ -15,13 +28,10 @@ lazy_static::lazy_static! {
 
 // Utility functions
 pub async fn get_subaccount_balance(subaccount: Vec<u8>) -> Result<Nat, ClaimError> {
-    let ledger_id = LEDGER_CANISTER_ID.with(|cell| {
-        let id = cell.borrow().get().clone();
-        if id == Principal::anonymous() {
-            return Err(ClaimError::LedgerNotSet);
-        }
-        Ok(id)
-    })?;
+    let ledger_id = effective_ledger_canister_id();
+    if ledger_id == Principal::anonymous() {
+        return Err(ClaimError::LedgerNotSet);
+    }
 
     let account = Account {
         owner: ic_cdk::api::id(),
@@ -41,13 +51,10 @@ pub async fn transfer_icrc1(
     to: Principal,
     gross_amount: Nat,
 ) -> Result<(), ClaimError> {
-    let ledger_id = LEDGER_CANISTER_ID.with(|cell| {
-        let id = cell.borrow().get().clone();
-        if id == Principal::anonymous() {
-            return Err(ClaimError::LedgerNotSet);
-        }
-        Ok(id)
-    })?;
+    let ledger_id = effective_ledger_canister_id();
+    if ledger_id == Principal::anonymous() {
+        return Err(ClaimError::LedgerNotSet);
+    }
 
     if gross_amount <= TRANSFER_FEE.clone() {
         return Ok(());
@@ -92,8 +99,20 @@ pub fn init(
         approvers.borrow_mut().insert(owner, true);
     });
 
-    CLAIM_DEPOSIT.with(|cell| {
-        cell.borrow_mut().set(StorableNat(claim_deposit)).ok();
+    APPROVER_ADDED_AT.with(|added| {
+        added.borrow_mut().insert(owner, ic_cdk::api::time());
+    });
+
+    // The initial deposit is flat; a proportional schedule can be set afterwards.
+    DEPOSIT_SCHEDULE.with(|cell| {
+        cell.borrow_mut()
+            .set(DepositSchedule {
+                base: claim_deposit.clone(),
+                bps: 0,
+                min: claim_deposit.clone(),
+                max: claim_deposit,
+            })
+            .ok();
     });
 
     LEDGER_CANISTER_ID.with(|cell| {
@@ -107,6 +126,16 @@ pub fn init(
     EXECUTION_TIMEOUT.with(|cell| {
         cell.borrow_mut().set(execution_timeout).ok();
     });
+
+    setup_reconciliation_timer();
+}
+
+#[ic_cdk::post_upgrade]
+pub fn post_upgrade() {
+    migrate_legacy_claim_deposit();
+    migrate_claims();
+    index_executing_claims();
+    setup_reconciliation_timer();
 }
 
 ic_cdk::export_candid!();
@@ -0,0 +1,263 @@
+use candid::Principal;
+use ic_cdk::api::call::call;
+
+use crate::decisions::record_decision;
+use crate::governance::process_config_changes;
+use crate::pools::{approval_period_for, execution_timeout_for, get_registered_pool_config};
+use crate::storage::*;
+use crate::types::*;
+
+// Parametric payouts skip human review, so only a short window is left to catch a bad report.
+pub const PARAMETRIC_EXECUTION_TIMEOUT: u64 = 60 * 60 * 1_000_000_000;
+
+pub(crate) fn claim_execution_timeout(claim: &Claim) -> u64 {
+    let pool_timeout = execution_timeout_for(claim.pool_canister_id);
+
+    if claim.incident_id.is_some() {
+        pool_timeout.min(PARAMETRIC_EXECUTION_TIMEOUT)
+    } else {
+        pool_timeout
+    }
+}
+
+// Claims filed for another incident of the product are left to the approvers.
+fn incident_covers_claim(incident: &IncidentReport, claim: &Claim) -> bool {
+    incident.pool_incident_id.is_some()
+        && claim.pool_incident_id == incident.pool_incident_id
+        && incident.pool_canister_id == claim.pool_canister_id
+        && incident.product_id == claim.product_id
+        && claim.coverage_start <= incident.incident_time
+        && incident.incident_time < claim.coverage_end
+}
+
+// Only pending claims that could still be approved by a human are picked up.
+fn auto_approve(claim: &mut Claim, incident: &IncidentReport) -> bool {
+    let current_time = ic_cdk::api::time();
+
+    if claim.status != ClaimStatus::Pending
+        || current_time > claim.created_at + approval_period_for(claim.pool_canister_id)
+        || !incident_covers_claim(incident, claim)
+    {
+        return false;
+    }
+
+    claim.status = ClaimStatus::Approved;
+    claim.approved_at = Some(current_time);
+    claim.approved_by = Some(incident.oracle);
+    claim.approved_amount = Some(claim.amount.clone());
+    claim.incident_id = Some(incident.id);
+    true
+}
+
+fn record_auto_approval(claim_id: u64, incident: &IncidentReport) {
+    record_decision(
+        claim_id,
+        incident.oracle,
+        DecisionKind::Approve,
+        format!(
+            "Parametric trigger {} reported in incident {}",
+            incident.trigger_id, incident.id
+        ),
+    );
+}
+
+// Claims filed after an incident was reported are approved as soon as they are created.
+pub(crate) fn apply_reported_incidents(claim_id: u64) {
+    let Some(mut claim) = CLAIMS.with(|claims| claims.borrow().get(&claim_id)) else {
+        return;
+    };
+
+    let incident = INCIDENTS.with(|incidents| {
+        incidents
+            .borrow()
+            .iter()
+            .map(|(_, incident)| incident)
+            .find(|incident| incident_covers_claim(incident, &claim))
+    });
+
+    if let Some(incident) = incident {
+        if auto_approve(&mut claim, &incident) {
+            CLAIMS.with(|claims| {
+                claims.borrow_mut().insert(claim_id, claim);
+            });
+            record_auto_approval(claim_id, &incident);
+        }
+    }
+}
+
+#[ic_cdk::query]
+pub fn is_oracle(principal: Principal) -> bool {
+    ORACLES.with(|oracles| oracles.borrow().get(&principal).unwrap_or(false))
+}
+
+#[ic_cdk::query]
+pub fn get_incident(incident_id: u64) -> Option<IncidentReport> {
+    INCIDENTS.with(|incidents| incidents.borrow().get(&incident_id))
+}
+
+#[ic_cdk::query]
+pub fn get_incidents() -> Vec<IncidentReport> {
+    INCIDENTS.with(|incidents| {
+        incidents
+            .borrow()
+            .iter()
+            .map(|(_, incident)| incident)
+            .collect()
+    })
+}
+
+#[ic_cdk::update]
+pub fn add_oracle(oracle: Principal) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    ORACLES.with(|oracles| {
+        oracles.borrow_mut().insert(oracle, true);
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn remove_oracle(oracle: Principal) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    ORACLES.with(|oracles| {
+        oracles.borrow_mut().remove(&oracle);
+    });
+
+    Ok(())
+}
+
+async fn get_pool_product(
+    pool_canister_id: Principal,
+    product_id: u64,
+) -> Result<Product, ClaimError> {
+    let product_result: Result<(Option<Product>,), _> =
+        call(pool_canister_id, "get_product", (product_id,)).await;
+
+    match product_result {
+        Ok((Some(product),)) => Ok(product),
+        Ok((None,)) => Err(ClaimError::ProductNotFound),
+        Err(e) => Err(ClaimError::PoolCallFailed(format!("{:?}", e))),
+    }
+}
+
+pub(crate) async fn report_pool_incident(
+    pool_canister_id: Principal,
+    product_id: u64,
+    incident_time: u64,
+    severity: IncidentSeverity,
+) -> Result<u64, ClaimError> {
+    let report_result: Result<(Result<u64, PoolError>,), _> = call(
+        pool_canister_id,
+        "report_incident",
+        (product_id, incident_time / 1_000_000_000, severity),
+    )
+    .await;
+
+    match report_result {
+        Ok((Ok(pool_incident_id),)) => Ok(pool_incident_id),
+        other => Err(ClaimError::PoolCallFailed(format!("{:?}", other))),
+    }
+}
+
+// Reports are authenticated by the calling oracle canister's principal. Every pending claim
+// filed for the incident this records in the pool's registry is approved in full.
+#[ic_cdk::update]
+pub async fn report_incident(
+    pool_canister_id: Principal,
+    product_id: u64,
+    trigger_id: String,
+    incident_time: u64,
+) -> Result<u64, ClaimError> {
+    process_config_changes();
+
+    let caller = ic_cdk::api::caller();
+
+    if !is_oracle(caller) {
+        return Err(ClaimError::NotOracle);
+    }
+
+    get_registered_pool_config(pool_canister_id)?;
+
+    if incident_time > ic_cdk::api::time() {
+        return Err(ClaimError::InvalidIncidentTime);
+    }
+
+    let product = get_pool_product(pool_canister_id, product_id).await?;
+
+    match product.parametric_trigger {
+        Some(trigger) if trigger.trigger_id == trigger_id => {}
+        _ => return Err(ClaimError::TriggerMismatch),
+    }
+
+    // The pool's registry freezes purchases of the product until the incident is resolved.
+    // A fired parametric trigger is an objectively verified loss event.
+    let pool_incident_id = report_pool_incident(
+        pool_canister_id,
+        product_id,
+        incident_time,
+        IncidentSeverity::High,
+    )
+    .await?;
+
+    // The oracle may have been removed while the pool was being called.
+    if !is_oracle(caller) {
+        return Err(ClaimError::NotOracle);
+    }
+
+    let incident_id = INCIDENT_COUNTER.with(|counter| {
+        let current = counter.borrow().get().clone();
+        let new_counter = current + 1;
+        counter.borrow_mut().set(new_counter).ok();
+        new_counter
+    });
+
+    let incident = IncidentReport {
+        id: incident_id,
+        oracle: caller,
+        pool_canister_id,
+        product_id,
+        trigger_id,
+        incident_time,
+        reported_at: ic_cdk::api::time(),
+        pool_incident_id: Some(pool_incident_id),
+    };
+
+    INCIDENTS.with(|incidents| {
+        incidents.borrow_mut().insert(incident_id, incident.clone());
+    });
+
+    let approved_claim_ids = CLAIMS.with(|claims| {
+        let mut claims_ref = claims.borrow_mut();
+        let approved: Vec<Claim> = claims_ref
+            .iter()
+            .filter_map(|(_, mut claim)| auto_approve(&mut claim, &incident).then_some(claim))
+            .collect();
+
+        approved
+            .into_iter()
+            .map(|claim| {
+                let claim_id = claim.id;
+                claims_ref.insert(claim_id, claim);
+                claim_id
+            })
+            .collect::<Vec<u64>>()
+    });
+
+    for claim_id in approved_claim_ids {
+        record_auto_approval(claim_id, &incident);
+    }
+
+    Ok(incident_id)
+}
@@ -0,0 +1,202 @@
+use candid::{Nat, Principal};
+
+use crate::deposits::{compute_deposit, validate_deposit_schedule};
+use crate::governance::{
+    cancel_pool_config_changes, effective_approval_period, effective_execution_timeout,
+    effective_pool, pending_pool_approvers, process_config_changes, queue_config_change,
+    CONFIG_CHANGE_DELAY,
+};
+use crate::storage::*;
+use crate::types::*;
+
+pub(crate) fn get_registered_pool_config(
+    pool_canister_id: Principal,
+) -> Result<RegisteredPool, ClaimError> {
+    effective_pool(pool_canister_id).ok_or(ClaimError::PoolNotRegistered)
+}
+
+// Per-pool overrides fall back to the canister-wide defaults, also for pools that were
+// unregistered while claims against them were still in flight.
+pub(crate) fn approval_period_for(pool_canister_id: Principal) -> u64 {
+    effective_pool(pool_canister_id)
+        .and_then(|pool| pool.approval_period)
+        .unwrap_or_else(effective_approval_period)
+}
+
+pub(crate) fn execution_timeout_for(pool_canister_id: Principal) -> u64 {
+    effective_pool(pool_canister_id)
+        .and_then(|pool| pool.execution_timeout)
+        .unwrap_or_else(effective_execution_timeout)
+}
+
+pub(crate) fn claim_deposit_for(pool_canister_id: Principal, amount: &Nat) -> Nat {
+    let schedule = effective_pool(pool_canister_id)
+        .and_then(|pool| pool.deposit_schedule)
+        .unwrap_or_else(|| DEPOSIT_SCHEDULE.with(|cell| cell.borrow().get().clone()));
+
+    compute_deposit(&schedule, amount)
+}
+
+// A pool with its own approver set is decided only by that set; otherwise the global approvers apply.
+pub(crate) fn is_pool_approver(pool_canister_id: Principal, principal: Principal) -> bool {
+    let pool_approvers = effective_pool(pool_canister_id)
+        .map(|pool| pool.approvers)
+        .unwrap_or_default();
+
+    if pool_approvers.is_empty() {
+        APPROVERS.with(|approvers| approvers.borrow().get(&principal).unwrap_or(false))
+    } else {
+        pool_approvers.contains(&principal)
+    }
+}
+
+// The approver timestamp backs the cooling-off rule, so it is only dropped once no role is left.
+pub(crate) fn forget_approver_if_unused(principal: Principal) {
+    let global = APPROVERS.with(|approvers| approvers.borrow().get(&principal).unwrap_or(false));
+    let pool = POOLS.with(|pools| {
+        pools
+            .borrow()
+            .iter()
+            .any(|(_, pool)| pool.approvers.contains(&principal))
+    });
+    let pending = pending_pool_approvers().contains(&principal);
+
+    if !global && !pool && !pending {
+        APPROVER_ADDED_AT.with(|added| {
+            added.borrow_mut().remove(&principal);
+        });
+    }
+}
+
+#[ic_cdk::update]
+pub fn register_pool(
+    pool_canister_id: Principal,
+    approval_period: Option<u64>,
+    execution_timeout: Option<u64>,
+    deposit_schedule: Option<DepositSchedule>,
+    approvers: Vec<Principal>,
+) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    if let Some(schedule) = &deposit_schedule {
+        validate_deposit_schedule(schedule)?;
+    }
+
+    if approval_period == Some(0) || execution_timeout == Some(0) {
+        return Err(ClaimError::InvalidConfig);
+    }
+
+    process_config_changes();
+
+    let current_time = ic_cdk::api::time();
+
+    let previous = POOLS.with(|pools| pools.borrow().get(&pool_canister_id));
+    let registered_at = previous
+        .as_ref()
+        .map_or(current_time, |pool| pool.registered_at);
+
+    // Changes to an already registered pool affect claims in flight, so they are queued.
+    let active_pool = match &previous {
+        Some(pool) => {
+            cancel_pool_config_changes(pool_canister_id);
+            // Queued approvers only count as added once the change applies.
+            APPROVER_ADDED_AT.with(|added| {
+                let mut added_ref = added.borrow_mut();
+                for approver in &approvers {
+                    if !added_ref.contains_key(approver) {
+                        added_ref.insert(*approver, current_time + CONFIG_CHANGE_DELAY);
+                    }
+                }
+            });
+            if pool.approval_period != approval_period {
+                queue_config_change(ConfigChange::PoolApprovalPeriod {
+                    pool_canister_id,
+                    approval_period,
+                })?;
+            }
+            if pool.execution_timeout != execution_timeout {
+                queue_config_change(ConfigChange::PoolExecutionTimeout {
+                    pool_canister_id,
+                    execution_timeout,
+                })?;
+            }
+            if pool.deposit_schedule != deposit_schedule {
+                queue_config_change(ConfigChange::PoolDepositSchedule {
+                    pool_canister_id,
+                    deposit_schedule,
+                })?;
+            }
+            if pool.approvers != approvers {
+                queue_config_change(ConfigChange::PoolApprovers {
+                    pool_canister_id,
+                    approvers,
+                })?;
+            }
+            pool.clone()
+        }
+        None => {
+            APPROVER_ADDED_AT.with(|added| {
+                let mut added_ref = added.borrow_mut();
+                for approver in &approvers {
+                    if !added_ref.contains_key(approver) {
+                        added_ref.insert(*approver, current_time);
+                    }
+                }
+            });
+            RegisteredPool {
+                pool_canister_id,
+                approval_period,
+                execution_timeout,
+                deposit_schedule,
+                approvers,
+                registered_at,
+            }
+        }
+    };
+
+    POOLS.with(|pools| {
+        pools.borrow_mut().insert(pool_canister_id, active_pool);
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn unregister_pool(pool_canister_id: Principal) -> Result<(), ClaimError> {
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    let pool = POOLS
+        .with(|pools| pools.borrow_mut().remove(&pool_canister_id))
+        .ok_or(ClaimError::PoolNotRegistered)?;
+
+    cancel_pool_config_changes(pool_canister_id);
+
+    for approver in pool.approvers {
+        forget_approver_if_unused(approver);
+    }
+
+    Ok(())
+}
+
+#[ic_cdk::query]
+pub fn get_registered_pool(pool_canister_id: Principal) -> Option<RegisteredPool> {
+    effective_pool(pool_canister_id)
+}
+
+#[ic_cdk::query]
+pub fn get_registered_pools() -> Vec<RegisteredPool> {
+    let pool_ids: Vec<Principal> =
+        POOLS.with(|pools| pools.borrow().iter().map(|(pool_id, _)| pool_id).collect());
+
+    pool_ids.into_iter().filter_map(effective_pool).collect()
+}
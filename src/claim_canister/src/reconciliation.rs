@@ -0,0 +1,119 @@
+use candid::Principal;
+use ic_cdk::api::call::call;
+use std::time::Duration;
+
+use crate::claims::set_claim_status;
+use crate::governance::process_config_changes;
+use crate::storage::*;
+use crate::types::*;
+
+// How often the timer looks for claims stuck in `Executing`.
+const RECONCILIATION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+// Claims are only reconciled by the timer once their slash call had time to complete.
+const RECONCILIATION_DELAY: u64 = 60 * 60 * 1_000_000_000;
+
+// Claims left in `Executing` by a version without the index are picked up again.
+pub fn index_executing_claims() {
+    let executing_claims: Vec<(u64, u64)> = CLAIMS.with(|claims| {
+        claims
+            .borrow()
+            .iter()
+            .filter(|(_, claim)| claim.status == ClaimStatus::Executing)
+            .map(|(claim_id, claim)| (claim_id, claim.execution_started_at.unwrap_or(0)))
+            .collect()
+    });
+
+    EXECUTING_CLAIMS.with(|executing| {
+        let mut executing_ref = executing.borrow_mut();
+        for (claim_id, started_at) in executing_claims {
+            executing_ref.insert(claim_id, started_at);
+        }
+    });
+}
+
+pub fn setup_reconciliation_timer() {
+    ic_cdk_timers::set_timer_interval(RECONCILIATION_INTERVAL, || {
+        ic_cdk::spawn(reconcile_stale_claims());
+    });
+}
+
+async fn reconcile_stale_claims() {
+    process_config_changes();
+
+    let current_time = ic_cdk::api::time();
+    let stale_claims: Vec<u64> = EXECUTING_CLAIMS.with(|executing| {
+        executing
+            .borrow()
+            .iter()
+            .filter(|(_, started_at)| started_at + RECONCILIATION_DELAY <= current_time)
+            .map(|(claim_id, _)| claim_id)
+            .collect()
+    });
+
+    for claim_id in stale_claims {
+        let _ = reconcile_executing_claim(claim_id).await;
+    }
+}
+
+#[ic_cdk::update]
+pub async fn reconcile_claim(claim_id: u64) -> Result<ClaimStatus, ClaimError> {
+    let caller = ic_cdk::api::caller();
+    let owner = OWNER.with(|cell| cell.borrow().get().clone());
+
+    if caller != owner {
+        return Err(ClaimError::InsufficientPermissions);
+    }
+
+    reconcile_executing_claim(claim_id).await
+}
+
+async fn reconcile_executing_claim(claim_id: u64) -> Result<ClaimStatus, ClaimError> {
+    let (pool_canister_id, started_at): (Principal, u64) = CLAIMS.with(|claims| {
+        let claim = claims.borrow().get(&claim_id).ok_or(ClaimError::NotFound)?;
+        if claim.status != ClaimStatus::Executing {
+            EXECUTING_CLAIMS.with(|executing| {
+                executing.borrow_mut().remove(&claim_id);
+            });
+            return Err(ClaimError::NotExecuting);
+        }
+        Ok((
+            claim.pool_canister_id,
+            claim.execution_started_at.unwrap_or(0),
+        ))
+    })?;
+
+    let record_result: Result<(Option<SlashRecord>,), _> =
+        call(pool_canister_id, "get_slash_record", (claim_id,)).await;
+
+    let record = match record_result {
+        Ok((record,)) => record,
+        Err(e) => return Err(ClaimError::PoolCallFailed(format!("{:?}", e))),
+    };
+
+    // A missing record means the slash never reached the pool, and a failed transfer is retried
+    // by the pool on the next slash call, so both cases can safely go back to `Approved`. A slash
+    // started less than the delay ago may still be on its way to the pool, so it is left alone.
+    let slash_may_be_in_flight = started_at + RECONCILIATION_DELAY > ic_cdk::api::time();
+    let resolved_status = match record.map(|record| record.status) {
+        Some(SlashStatus::Completed) => ClaimStatus::Executed,
+        Some(SlashStatus::InProgress) => ClaimStatus::Executing,
+        None if slash_may_be_in_flight => ClaimStatus::Executing,
+        Some(SlashStatus::TransferFailed) | None => ClaimStatus::Approved,
+    };
+
+    // The original execute_claim callback may have landed while we awaited the pool.
+    let current_status = CLAIMS.with(|claims| {
+        claims
+            .borrow()
+            .get(&claim_id)
+            .map(|claim| claim.status)
+            .ok_or(ClaimError::NotFound)
+    })?;
+
+    if current_status != ClaimStatus::Executing {
+        return Ok(current_status);
+    }
+
+    set_claim_status(claim_id, resolved_status.clone());
+    Ok(resolved_status)
+}
@@ -1,4 +1,8 @@
-use crate::types::{Claim, Memory, StorableNat};
+use crate::types::{
+    Appeal, Claim, ClaimComments, ClaimEvidence, ClaimIds, ConflictRules, DecisionIds,
+    DecisionRecord, Delegates, DepositSchedule, IncidentReport, Memory, PendingConfigChanges,
+    RegisteredPool, StorableNat,
+};
 use candid::{Nat, Principal};
 use ic_stable_structures::memory_manager::{MemoryId, MemoryManager};
 use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, StableCell};
@@ -34,11 +38,12 @@ thread_local! {
         ).expect("Failed to initialize OWNER")
     );
 
-    pub static CLAIM_DEPOSIT: RefCell<StableCell<StorableNat, Memory>> = RefCell::new(
+    // Flat deposit from before deposit schedules; only read to migrate it on upgrade.
+    pub static LEGACY_CLAIM_DEPOSIT: RefCell<StableCell<StorableNat, Memory>> = RefCell::new(
         StableCell::init(
             MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(5))),
             StorableNat(Nat::from(0u64))
-        ).expect("Failed to initialize CLAIM_DEPOSIT")
+        ).expect("Failed to initialize LEGACY_CLAIM_DEPOSIT")
     );
 
     pub static LEDGER_CANISTER_ID: RefCell<StableCell<Principal, Memory>> = RefCell::new(
@@ -61,4 +66,162 @@ thread_local! {
             0u64
         ).expect("Failed to initialize EXECUTION_TIMEOUT")
     );
+
+    pub static CLAIM_EVIDENCE: RefCell<StableBTreeMap<u64, ClaimEvidence, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(9))),
+        )
+    );
+
+    pub static CLAIM_COMMENTS: RefCell<StableBTreeMap<u64, ClaimComments, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(10))),
+        )
+    );
+
+    pub static DECISION_COUNTER: RefCell<StableCell<u64, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(11))),
+            0u64
+        ).expect("Failed to initialize DECISION_COUNTER")
+    );
+
+    pub static DECISIONS: RefCell<StableBTreeMap<u64, DecisionRecord, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(12))),
+        )
+    );
+
+    pub static CLAIM_DECISIONS: RefCell<StableBTreeMap<u64, DecisionIds, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(13))),
+        )
+    );
+
+    pub static APPROVER_DECISIONS: RefCell<StableBTreeMap<Principal, DecisionIds, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(14))),
+        )
+    );
+
+    pub static CONFLICT_RULES: RefCell<StableCell<ConflictRules, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(15))),
+            ConflictRules {
+                exclude_proposer: true,
+                exclude_receiver: true,
+                cooling_off_period: 0,
+            }
+        ).expect("Failed to initialize CONFLICT_RULES")
+    );
+
+    pub static APPROVER_ADDED_AT: RefCell<StableBTreeMap<Principal, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(16))),
+        )
+    );
+
+    pub static POOLS: RefCell<StableBTreeMap<Principal, RegisteredPool, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(17))),
+        )
+    );
+
+    pub static APPEAL_COMMITTEE: RefCell<StableBTreeMap<Principal, bool, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(18))),
+        )
+    );
+
+    pub static APPEAL_DEPOSIT: RefCell<StableCell<StorableNat, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(19))),
+            StorableNat(Nat::from(0u64))
+        ).expect("Failed to initialize APPEAL_DEPOSIT")
+    );
+
+    pub static APPEAL_QUORUM: RefCell<StableCell<u64, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(20))),
+            1u64
+        ).expect("Failed to initialize APPEAL_QUORUM")
+    );
+
+    pub static APPEALS: RefCell<StableBTreeMap<u64, Appeal, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(21))),
+        )
+    );
+
+    pub static DEPOSIT_SCHEDULE: RefCell<StableCell<DepositSchedule, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(22))),
+            DepositSchedule {
+                base: Nat::from(0u64),
+                bps: 0,
+                min: Nat::from(0u64),
+                max: Nat::from(0u64),
+            }
+        ).expect("Failed to initialize DEPOSIT_SCHEDULE")
+    );
+
+    // `Principal::anonymous()` means no ownership transfer is in progress.
+    pub static PENDING_OWNER: RefCell<StableCell<Principal, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(23))),
+            Principal::anonymous()
+        ).expect("Failed to initialize PENDING_OWNER")
+    );
+
+    pub static PENDING_CONFIG_CHANGES: RefCell<StableCell<PendingConfigChanges, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(24))),
+            PendingConfigChanges(vec![])
+        ).expect("Failed to initialize PENDING_CONFIG_CHANGES")
+    );
+
+    pub static DELEGATES: RefCell<StableBTreeMap<Principal, Delegates, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(25))),
+        )
+    );
+
+    pub static PAYOUT_ACCOUNTS: RefCell<StableBTreeMap<Principal, Principal, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(26))),
+        )
+    );
+
+    pub static ORACLES: RefCell<StableBTreeMap<Principal, bool, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(27))),
+        )
+    );
+
+    pub static INCIDENT_COUNTER: RefCell<StableCell<u64, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(28))),
+            0u64
+        ).expect("Failed to initialize INCIDENT_COUNTER")
+    );
+
+    pub static INCIDENTS: RefCell<StableBTreeMap<u64, IncidentReport, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(29))),
+        )
+    );
+
+    // Claims currently in `Executing`, with the time their execution started.
+    pub static EXECUTING_CLAIMS: RefCell<StableBTreeMap<u64, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(30))),
+        )
+    );
+
+    // Claims filed against each coverage, keyed by pool and coverage id.
+    pub static COVERAGE_CLAIMS: RefCell<StableBTreeMap<(Principal, u64), ClaimIds, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(31))),
+        )
+    );
 }
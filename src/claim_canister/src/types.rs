@@ -60,6 +60,9 @@ pub enum ClaimStatus {
     Executed,
     Rejected,
     Spam,
+    PartiallyApproved,
+    Disputed,
+    Appealed,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
@@ -75,6 +78,66 @@ pub struct Claim {
     pub approved_at: Option<u64>,
     pub approved_by: Option<Principal>,
     pub deposit_amount: Nat,
+    pub execution_started_at: Option<u64>,
+    pub approved_amount: Option<Nat>,
+    // `None` for claims filed before claims were bound to coverages
+    pub coverage_id: Option<u64>,
+    pub covered_account: Principal,
+    pub product_id: u64,
+    // Coverage window in nanoseconds, used to match incident reports
+    pub coverage_start: u64,
+    pub coverage_end: u64,
+    pub incident_id: Option<u64>,
+    pub incident_time: u64,
+    // Incident in the pool's registry the claim was filed for
+    pub pool_incident_id: Option<u64>,
+}
+
+// Shape of claims stored before they were bound to coverages.
+#[derive(CandidType, Deserialize)]
+struct LegacyClaim {
+    id: u64,
+    proposer: Principal,
+    receiver: Principal,
+    amount: Nat,
+    pool_canister_id: Principal,
+    description: String,
+    status: ClaimStatus,
+    created_at: u64,
+    approved_at: Option<u64>,
+    approved_by: Option<Principal>,
+    deposit_amount: Nat,
+    execution_started_at: Option<u64>,
+    approved_amount: Option<Nat>,
+}
+
+impl From<LegacyClaim> for Claim {
+    fn from(legacy: LegacyClaim) -> Self {
+        // An empty coverage window keeps legacy claims out of incident matching.
+        Claim {
+            id: legacy.id,
+            proposer: legacy.proposer,
+            receiver: legacy.receiver,
+            amount: legacy.amount,
+            pool_canister_id: legacy.pool_canister_id,
+            description: legacy.description,
+            status: legacy.status,
+            created_at: legacy.created_at,
+            approved_at: legacy.approved_at,
+            approved_by: legacy.approved_by,
+            deposit_amount: legacy.deposit_amount,
+            execution_started_at: legacy.execution_started_at,
+            approved_amount: legacy.approved_amount,
+            coverage_id: None,
+            covered_account: legacy.receiver,
+            product_id: 0,
+            coverage_start: 0,
+            coverage_end: 0,
+            incident_id: None,
+            incident_time: legacy.created_at,
+            pool_incident_id: None,
+        }
+    }
 }
 
 impl Storable for Claim {
@@ -82,6 +145,219 @@ impl Storable for Claim {
         Cow::Owned(candid::encode_one(self).unwrap())
     }
 
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes)
+            .unwrap_or_else(|_| candid::decode_one::<LegacyClaim>(&bytes).unwrap().into())
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub struct EvidenceItem {
+    pub content_hash: Vec<u8>,
+    pub mime_type: String,
+    pub uri: String,
+    pub submitter: Principal,
+    pub submitted_at: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct ClaimEvidence(pub Vec<EvidenceItem>);
+
+impl Storable for ClaimEvidence {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(&self.0).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        ClaimEvidence(candid::decode_one(&bytes).unwrap())
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub struct ClaimComment {
+    pub author: Principal,
+    pub text: String,
+    pub created_at: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct ClaimComments(pub Vec<ClaimComment>);
+
+impl Storable for ClaimComments {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(&self.0).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        ClaimComments(candid::decode_one(&bytes).unwrap())
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub enum DecisionKind {
+    Approve,
+    Reject,
+    Spam,
+    Dispute,
+    Appeal,
+    AppealVote { uphold: bool },
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub struct DecisionRecord {
+    pub id: u64,
+    pub claim_id: u64,
+    pub actor: Principal,
+    pub kind: DecisionKind,
+    pub rationale: String,
+    pub decided_at: u64,
+}
+
+impl Storable for DecisionRecord {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(Clone, Debug)]
+pub struct DecisionIds(pub Vec<u64>);
+
+impl Storable for DecisionIds {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(&self.0).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        DecisionIds(candid::decode_one(&bytes).unwrap())
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(Clone, Debug)]
+pub struct ClaimIds(pub Vec<u64>);
+
+impl Storable for ClaimIds {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(&self.0).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        ClaimIds(candid::decode_one(&bytes).unwrap())
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub struct ConflictRules {
+    pub exclude_proposer: bool,
+    pub exclude_receiver: bool,
+    pub cooling_off_period: u64,
+}
+
+impl Storable for ConflictRules {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum IneligibilityReason {
+    NotApprover,
+    Proposer,
+    Receiver,
+    CoolingOff { eligible_at: u64 },
+    OriginalDecisionMaker,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub struct DepositSchedule {
+    pub base: Nat,
+    pub bps: u64,
+    pub min: Nat,
+    pub max: Nat,
+}
+
+impl Storable for DepositSchedule {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub struct RegisteredPool {
+    pub pool_canister_id: Principal,
+    pub approval_period: Option<u64>,
+    pub execution_timeout: Option<u64>,
+    pub deposit_schedule: Option<DepositSchedule>,
+    pub approvers: Vec<Principal>,
+    pub registered_at: u64,
+}
+
+impl Storable for RegisteredPool {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub enum AppealStatus {
+    Open,
+    Upheld,
+    Dismissed,
+    Expired,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub struct Appeal {
+    pub claim_id: u64,
+    pub previous_status: ClaimStatus,
+    pub status: AppealStatus,
+    pub deposit_amount: Nat,
+    pub opened_at: u64,
+    pub resolved_at: Option<u64>,
+    pub uphold_votes: Vec<Principal>,
+    pub dismiss_votes: Vec<Principal>,
+    // Cover of the claim's coverage, `None` for claims not bound to one
+    pub coverage_amount: Option<Nat>,
+}
+
+impl Storable for Appeal {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
     fn from_bytes(bytes: Cow<[u8]>) -> Self {
         candid::decode_one(&bytes).unwrap()
     }
@@ -89,6 +365,66 @@ impl Storable for Claim {
     const BOUND: Bound = Bound::Unbounded;
 }
 
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub enum ConfigChange {
+    ApprovalPeriod(u64),
+    ExecutionTimeout(u64),
+    LedgerCanisterId(Principal),
+    PoolApprovalPeriod {
+        pool_canister_id: Principal,
+        approval_period: Option<u64>,
+    },
+    PoolExecutionTimeout {
+        pool_canister_id: Principal,
+        execution_timeout: Option<u64>,
+    },
+    ConflictRules(ConflictRules),
+    AppealQuorum(u64),
+    PoolApprovers {
+        pool_canister_id: Principal,
+        approvers: Vec<Principal>,
+    },
+    PoolDepositSchedule {
+        pool_canister_id: Principal,
+        deposit_schedule: Option<DepositSchedule>,
+    },
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub struct PendingConfigChange {
+    pub change: ConfigChange,
+    pub effective_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub struct PendingConfigChanges(pub Vec<PendingConfigChange>);
+
+impl Storable for PendingConfigChanges {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(&self.0).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        PendingConfigChanges(candid::decode_one(&bytes).unwrap())
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ClaimConfig {
+    pub owner: Principal,
+    pub pending_owner: Option<Principal>,
+    pub ledger_canister_id: Principal,
+    pub approval_period: u64,
+    pub execution_timeout: u64,
+    pub deposit_schedule: DepositSchedule,
+    pub conflict_rules: ConflictRules,
+    pub appeal_deposit: Nat,
+    pub appeal_quorum: u64,
+    pub pending_changes: Vec<PendingConfigChange>,
+}
+
 #[derive(CandidType, Deserialize, Debug, PartialEq)]
 pub enum ClaimError {
     NotFound,
@@ -109,6 +445,46 @@ pub enum ClaimError {
     DepositTransferFailed,
     InsufficientDeposit,
     LedgerNotSet,
+    NotExecuting,
+    ClaimNotPending,
+    InvalidEvidence,
+    EvidenceLimitReached,
+    InvalidComment,
+    CommentLimitReached,
+    InvalidRationale,
+    ApproverIneligible(IneligibilityReason),
+    PoolNotRegistered,
+    InvalidApprovedAmount,
+    NotPartiallyApproved,
+    NotAppealable,
+    AppealAlreadyOpened,
+    AppealNotOpen,
+    AppealNotUpheld,
+    AppealInProgress,
+    NotAppealCommitteeMember,
+    AlreadyVoted,
+    InvalidQuorum,
+    InvalidDepositSchedule,
+    InvalidConfig,
+    NotPendingOwner,
+    CoverageNotFound,
+    NotAuthorizedForCoverage,
+    ClaimExceedsCoverage,
+    DelegateLimitReached,
+    InvalidPayoutAccount,
+    NotOracle,
+    ProductNotFound,
+    TriggerMismatch,
+    InvalidIncidentTime,
+    IncidentOutsideCoverage,
+    IncidentInWaitingPeriod,
+    ClaimDeadlinePassed,
+    AppealExpired,
+    AppealVotingPeriodNotExpired,
+    AppealNotDismissed,
+    IncidentNotFound,
+    IncidentNotForProduct,
+    CoverageCancelled,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
@@ -124,6 +500,17 @@ pub struct ClaimInfo {
     pub approved_at: Option<u64>,
     pub approved_by: Option<Principal>,
     pub deposit_amount: Nat,
+    pub execution_started_at: Option<u64>,
+    pub approved_amount: Option<Nat>,
+    pub coverage_id: Option<u64>,
+    pub covered_account: Principal,
+    pub product_id: u64,
+    // Coverage window in nanoseconds, used to match incident reports
+    pub coverage_start: u64,
+    pub coverage_end: u64,
+    pub incident_id: Option<u64>,
+    pub incident_time: u64,
+    pub pool_incident_id: Option<u64>,
 }
 
 #[derive(CandidType, Deserialize, Debug, PartialEq)]
@@ -138,4 +525,110 @@ pub enum PoolError {
     EpisodeNotActive,
     EpisodeNotStakable,
     NotSlashingExecutor,
+    SlashInProgress,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum SlashStatus {
+    InProgress,
+    Completed,
+    TransferFailed,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SlashRecord {
+    pub receiver: Principal,
+    pub amount: Nat,
+    pub slashed_amount: Nat,
+    pub status: SlashStatus,
+    pub created_at: u64,
+}
+
+// Subset of the pool canister's coverage record
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Coverage {
+    pub coverage_id: u64,
+    pub buyer: Principal,
+    pub covered_account: Principal,
+    pub product_id: u64,
+    pub coverage_amount: Nat,
+    pub purchased_at: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub claim_deadline: u64,
+    pub status: CoverageStatus,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum CoverageStatus {
+    Active,
+    Cancelled,
+}
+
+// Subset of the pool canister's incident record, with times in seconds
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PoolIncident {
+    pub incident_id: u64,
+    pub product_id: u64,
+    pub incident_time: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum IncidentSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+#[derive(Clone, Debug)]
+pub struct Delegates(pub Vec<Principal>);
+
+impl Storable for Delegates {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(&self.0).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Delegates(candid::decode_one(&bytes).unwrap())
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+// Subset of the pool canister's product record
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Product {
+    pub product_id: u64,
+    pub parametric_trigger: Option<ParametricTrigger>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub struct ParametricTrigger {
+    pub trigger_id: String,
+    pub description: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub struct IncidentReport {
+    pub id: u64,
+    pub oracle: Principal,
+    pub pool_canister_id: Principal,
+    pub product_id: u64,
+    pub trigger_id: String,
+    pub incident_time: u64,
+    pub reported_at: u64,
+    pub pool_incident_id: Option<u64>,
+}
+
+impl Storable for IncidentReport {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
 }
@@ -1,18 +1,59 @@
 mod setup;
-use candid::{Nat, Principal};
-use claim_canister::types::{ClaimError, ClaimStatus};
-use commons::{utils::transfer_to_subaccount, ClaimCanisterClient, LedgerCanisterClient};
-use pool_canister::TRANSFER_FEE;
-use setup::setup;
+use candid::{encode_args, Decode, Nat, Principal};
+use claim_canister::appeals::APPEAL_VOTING_PERIOD;
+use claim_canister::conflicts::MAX_COOLING_OFF_PERIOD;
+use claim_canister::governance::CONFIG_CHANGE_DELAY;
+use claim_canister::parametric::PARAMETRIC_EXECUTION_TIMEOUT;
+use claim_canister::types::{
+    AppealStatus, ClaimError, ClaimStatus, ConfigChange, ConflictRules, DecisionKind,
+    DepositSchedule, IncidentSeverity as ClaimIncidentSeverity, IneligibilityReason,
+};
+use commons::{
+    utils::{calculate_premium, purchase_coverage, transfer_to_subaccount},
+    ClaimCanisterClient, LedgerCanisterClient, PoolCanisterClient,
+};
+use pool_canister::{IncidentSeverity, ProductState, EPISODE_DURATION, TRANSFER_FEE};
+use setup::{approver, install_mock_oracle, setup};
 use std::time::Duration;
 
 // Constants
 const APPROVAL_PERIOD_NANOS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000; // 7 days in nanoseconds
 const EXECUTION_TIMEOUT_NANOS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000; // 7 days in nanoseconds
 
+fn current_time_nanos(pic: &pocket_ic::PocketIc) -> u64 {
+    pic.get_time().as_nanos_since_unix_epoch()
+}
+
+// Records an incident for the coverage's product in the pool's registry, at the current time
+fn report_pool_incident(
+    pic: &pocket_ic::PocketIc,
+    pool_canister: Principal,
+    owner: Principal,
+    coverage_id: u64,
+) -> u64 {
+    let incident_time = current_time_nanos(pic) / 1_000_000_000;
+    report_pool_incident_at(pic, pool_canister, owner, coverage_id, incident_time)
+}
+
+// The owner is the pool manager, who may report incidents
+fn report_pool_incident_at(
+    pic: &pocket_ic::PocketIc,
+    pool_canister: Principal,
+    owner: Principal,
+    coverage_id: u64,
+    incident_time: u64,
+) -> u64 {
+    let mut pool_client = PoolCanisterClient::new(pic, pool_canister);
+    let product_id = pool_client.get_coverage(coverage_id).unwrap().product_id;
+    pool_client
+        .connect(owner)
+        .report_incident(product_id, incident_time, IncidentSeverity::Medium)
+        .expect("report_incident should succeed")
+}
+
 #[test]
 fn test_claim_positive_flow() {
-    let (pic, claim_canister, pool_canister, owner, ledger_id) = setup();
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
 
     let receiver_bytes = [3u8; 29];
     let receiver = Principal::from_slice(&receiver_bytes);
@@ -21,13 +62,20 @@ fn test_claim_positive_flow() {
 
     // Create claim client
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
     let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
 
+    // Payouts for the owner's coverage go to the receiver
+    claim_client
+        .connect(owner)
+        .set_payout_account(Some(receiver))
+        .expect("set_payout_account should succeed");
+
     // Transfer deposit to subaccount
     let deposit_amount = Nat::from(1_000_000u64);
     let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
         owner,
-        receiver,
+        coverage_id,
         amount.clone(),
         pool_canister,
         desc.clone(),
@@ -43,13 +91,19 @@ fn test_claim_positive_flow() {
     // Add claim
     let claim_id = claim_client
         .connect(owner)
-        .add_claim(receiver, amount.clone(), pool_canister, desc)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            amount.clone(),
+            pool_canister,
+            desc,
+        )
         .expect("add_claim should succeed");
 
     // Approve claim
     claim_client
-        .connect(owner)
-        .approve_claim(claim_id)
+        .connect(approver())
+        .approve_claim(claim_id, None, "Loss verified".to_string())
         .expect("approve_claim should succeed");
 
     // Verify claim is approved before execution timeout
@@ -99,7 +153,7 @@ fn test_claim_positive_flow() {
 
 #[test]
 fn test_execute_only_after_timelock() {
-    let (pic, claim_canister, pool_canister, owner, ledger_id) = setup();
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
 
     let receiver_bytes = [3u8; 29];
     let receiver = Principal::from_slice(&receiver_bytes);
@@ -107,13 +161,14 @@ fn test_execute_only_after_timelock() {
     let desc = String::from("Timelock test");
 
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
     let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
 
     // Transfer deposit to subaccount
     let deposit_amount = Nat::from(1_000_000u64);
     let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
         owner,
-        receiver,
+        coverage_id,
         amount.clone(),
         pool_canister,
         desc.clone(),
@@ -129,13 +184,13 @@ fn test_execute_only_after_timelock() {
     // Add claim
     let claim_id = claim_client
         .connect(owner)
-        .add_claim(receiver, amount, pool_canister, desc)
+        .add_claim(coverage_id, incident_id, amount, pool_canister, desc)
         .expect("add_claim should succeed");
 
     // Approve claim
     claim_client
-        .connect(owner)
-        .approve_claim(claim_id)
+        .connect(approver())
+        .approve_claim(claim_id, None, "Loss verified".to_string())
         .expect("approve_claim should succeed");
 
     // Try to execute before execution timeout - should fail
@@ -162,7 +217,7 @@ fn test_execute_only_after_timelock() {
 
 #[test]
 fn test_cannot_execute_same_claim_multiple_times() {
-    let (pic, claim_canister, pool_canister, owner, ledger_id) = setup();
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
 
     let receiver_bytes = [4u8; 29];
     let receiver = Principal::from_slice(&receiver_bytes);
@@ -170,13 +225,14 @@ fn test_cannot_execute_same_claim_multiple_times() {
     let desc = String::from("Double execute test");
 
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
     let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
 
     // Transfer deposit to subaccount
     let deposit_amount = Nat::from(1_000_000u64);
     let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
         owner,
-        receiver,
+        coverage_id,
         amount.clone(),
         pool_canister,
         desc.clone(),
@@ -192,12 +248,12 @@ fn test_cannot_execute_same_claim_multiple_times() {
     // Create and approve claim
     let claim_id = claim_client
         .connect(owner)
-        .add_claim(receiver, amount, pool_canister, desc)
+        .add_claim(coverage_id, incident_id, amount, pool_canister, desc)
         .expect("add_claim should succeed");
 
     claim_client
-        .connect(owner)
-        .approve_claim(claim_id)
+        .connect(approver())
+        .approve_claim(claim_id, None, "Loss verified".to_string())
         .expect("approve_claim should succeed");
 
     // Advance time past execution timeout
@@ -227,7 +283,7 @@ fn test_cannot_execute_same_claim_multiple_times() {
 
 #[test]
 fn test_execute_before_approval_not_possible() {
-    let (pic, claim_canister, pool_canister, owner, ledger_id) = setup();
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
 
     let receiver_bytes = [5u8; 29];
     let receiver = Principal::from_slice(&receiver_bytes);
@@ -235,13 +291,14 @@ fn test_execute_before_approval_not_possible() {
     let desc = String::from("Execute before approval");
 
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
     let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
 
     // Transfer deposit to subaccount
     let deposit_amount = Nat::from(1_000_000u64);
     let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
         owner,
-        receiver,
+        coverage_id,
         amount.clone(),
         pool_canister,
         desc.clone(),
@@ -257,7 +314,7 @@ fn test_execute_before_approval_not_possible() {
     // Create claim but don't approve
     let claim_id = claim_client
         .connect(owner)
-        .add_claim(receiver, amount, pool_canister, desc)
+        .add_claim(coverage_id, incident_id, amount, pool_canister, desc)
         .expect("add_claim should succeed");
 
     // Try to execute without approval - should fail
@@ -267,7 +324,7 @@ fn test_execute_before_approval_not_possible() {
 
 #[test]
 fn test_only_owner_can_change_approvers() {
-    let (pic, claim_canister, _pool_canister, owner, _ledger_id) = setup();
+    let (pic, claim_canister, _pool_canister, owner, _ledger_id, _coverage_id) = setup();
 
     let other_bytes = [9u8; 29];
     let other = Principal::from_slice(&other_bytes);
@@ -306,50 +363,65 @@ fn test_only_owner_can_change_approvers() {
 
 #[test]
 fn test_claim_status_reverts_to_approved_on_slash_failure() {
-    let (pic, claim_canister, pool_canister, owner, ledger_id) = setup();
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
 
     let receiver_bytes = [6u8; 29];
     let receiver = Principal::from_slice(&receiver_bytes);
-    let amount = Nat::from(200_000_000_000u64);
-    let desc = String::from("Slash failure test");
+    // Each claim is within the coverage, but together they exceed the pool's assets
+    let amount = Nat::from(60_000_000_000u64);
 
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
     let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
 
-    // Transfer deposit to subaccount
-    let deposit_amount = Nat::from(1_000_000u64);
-    let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
-        owner,
-        receiver,
-        amount.clone(),
-        pool_canister,
-        desc.clone(),
-    );
-    transfer_to_subaccount(
-        &mut ledger_client,
-        owner,
-        claim_canister,
-        subaccount,
-        deposit_amount,
-    );
-
-    // Create and approve claim
-    let claim_id = claim_client
-        .connect(owner)
-        .add_claim(receiver, amount, pool_canister, desc)
-        .expect("add_claim should succeed");
-
-    claim_client
-        .connect(owner)
-        .approve_claim(claim_id)
-        .expect("approve_claim should succeed");
+    // Create and approve two claims
+    let mut claim_ids = vec![];
+    for desc in ["Slash failure test", "Slash failure test, second loss"] {
+        let deposit_amount = Nat::from(1_000_000u64);
+        let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+            owner,
+            coverage_id,
+            amount.clone(),
+            pool_canister,
+            desc.to_string(),
+        );
+        transfer_to_subaccount(
+            &mut ledger_client,
+            owner,
+            claim_canister,
+            subaccount,
+            deposit_amount,
+        );
+
+        let claim_id = claim_client
+            .connect(owner)
+            .add_claim(
+                coverage_id,
+                incident_id,
+                amount.clone(),
+                pool_canister,
+                desc.to_string(),
+            )
+            .expect("add_claim should succeed");
+
+        claim_client
+            .connect(approver())
+            .approve_claim(claim_id, None, "Loss verified".to_string())
+            .expect("approve_claim should succeed");
+        claim_ids.push(claim_id);
+    }
 
     // Advance time past execution timeout
     let execution_timeout = Duration::from_nanos(EXECUTION_TIMEOUT_NANOS);
     pic.advance_time(execution_timeout);
 
-    // Execute claim (this will fail due to insufficient balance in pool)
-    let exec_result = claim_client.connect(receiver).execute_claim(claim_id);
+    claim_client
+        .connect(receiver)
+        .execute_claim(claim_ids[0])
+        .expect("execute_claim should succeed");
+
+    // Execute second claim (this will fail due to insufficient balance in pool)
+    let exec_result = claim_client.connect(receiver).execute_claim(claim_ids[1]);
     assert_eq!(
         exec_result,
         Err(ClaimError::PoolCallFailed(
@@ -360,23 +432,28 @@ fn test_claim_status_reverts_to_approved_on_slash_failure() {
     // Verify claim status reverted back to Approved
     let claim_info = claim_client
         .connect(owner)
-        .get_claim(claim_id)
+        .get_claim(claim_ids[1])
         .expect("expected claim to exist after failed slash");
     assert_eq!(claim_info.status, ClaimStatus::Approved);
 }
 
 #[test]
 fn test_claim_creation_requires_deposit() {
-    let (pic, claim_canister, pool_canister, owner, ledger_id) = setup();
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
 
     let proposer_bytes = [10u8; 29];
     let proposer = Principal::from_slice(&proposer_bytes);
-    let receiver_bytes = [11u8; 29];
-    let receiver = Principal::from_slice(&receiver_bytes);
 
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
     let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
 
+    // The owner holds the coverage and lets the proposer file claims for it
+    claim_client
+        .connect(owner)
+        .add_delegate(proposer)
+        .expect("add_delegate should succeed");
+
     // Fund the proposer
     let proposer_account = pool_canister::types::Account {
         owner: proposer,
@@ -392,8 +469,14 @@ fn test_claim_creation_requires_deposit() {
     };
     ledger_client.connect(owner).icrc1_transfer(transfer_args);
 
+    // Define claim parameters
+    let claim_amount = Nat::from(5_000_000u64);
+    let claim_desc = "Test claim with deposit".to_string();
+
     // Check the claim deposit requirement
-    let required_deposit = claim_client.connect(proposer).get_claim_deposit();
+    let required_deposit = claim_client
+        .connect(proposer)
+        .get_claim_deposit(pool_canister, claim_amount.clone());
     assert_eq!(required_deposit, Nat::from(1_000_000u64));
 
     // Get proposer balance before transferring to subaccount
@@ -401,14 +484,10 @@ fn test_claim_creation_requires_deposit() {
         .connect(proposer)
         .icrc1_balance_of(proposer_account.clone());
 
-    // Define claim parameters
-    let claim_amount = Nat::from(5_000_000u64);
-    let claim_desc = "Test claim with deposit".to_string();
-
     // Transfer deposit to proposer's subaccount
     let subaccount = claim_client.connect(proposer).get_claim_deposit_subaccount(
         proposer,
-        receiver,
+        coverage_id,
         claim_amount.clone(),
         pool_canister,
         claim_desc.clone(),
@@ -424,7 +503,13 @@ fn test_claim_creation_requires_deposit() {
     // Create claim (deposit already in subaccount)
     let claim_id = claim_client
         .connect(proposer)
-        .add_claim(receiver, claim_amount, pool_canister, claim_desc)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            claim_amount,
+            pool_canister,
+            claim_desc,
+        )
         .expect("add_claim should succeed");
 
     // Verify deposit was deducted from proposer's main account (when transferred to subaccount)
@@ -443,16 +528,21 @@ fn test_claim_creation_requires_deposit() {
 
 #[test]
 fn test_deposit_not_returned_on_approval() {
-    let (pic, claim_canister, pool_canister, owner, ledger_id) = setup();
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
 
     let proposer_bytes = [12u8; 29];
     let proposer = Principal::from_slice(&proposer_bytes);
-    let receiver_bytes = [13u8; 29];
-    let receiver = Principal::from_slice(&receiver_bytes);
 
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
     let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
 
+    // The owner holds the coverage and lets the proposer file claims for it
+    claim_client
+        .connect(owner)
+        .add_delegate(proposer)
+        .expect("add_delegate should succeed");
+
     // Fund the proposer
     let proposer_account = pool_canister::types::Account {
         owner: proposer,
@@ -476,7 +566,7 @@ fn test_deposit_not_returned_on_approval() {
     // Transfer deposit to proposer's subaccount
     let subaccount = claim_client.connect(proposer).get_claim_deposit_subaccount(
         proposer,
-        receiver,
+        coverage_id,
         claim_amount.clone(),
         pool_canister,
         claim_desc.clone(),
@@ -492,7 +582,13 @@ fn test_deposit_not_returned_on_approval() {
     // Create claim
     let claim_id = claim_client
         .connect(proposer)
-        .add_claim(receiver, claim_amount, pool_canister, claim_desc)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            claim_amount,
+            pool_canister,
+            claim_desc,
+        )
         .expect("add_claim should succeed");
 
     let balance_after_claim = ledger_client
@@ -501,8 +597,8 @@ fn test_deposit_not_returned_on_approval() {
 
     // Approve claim
     claim_client
-        .connect(owner)
-        .approve_claim(claim_id)
+        .connect(approver())
+        .approve_claim(claim_id, None, "Loss verified".to_string())
         .expect("approve_claim should succeed");
 
     // Verify deposit was NOT returned - balance should remain the same
@@ -514,16 +610,21 @@ fn test_deposit_not_returned_on_approval() {
 
 #[test]
 fn test_withdraw_deposit_from_pending_claim() {
-    let (pic, claim_canister, pool_canister, owner, ledger_id) = setup();
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
 
     let proposer_bytes = [14u8; 29];
     let proposer = Principal::from_slice(&proposer_bytes);
-    let receiver_bytes = [15u8; 29];
-    let receiver = Principal::from_slice(&receiver_bytes);
 
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
     let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
 
+    // The owner holds the coverage and lets the proposer file claims for it
+    claim_client
+        .connect(owner)
+        .add_delegate(proposer)
+        .expect("add_delegate should succeed");
+
     // Fund the proposer
     let proposer_account = pool_canister::types::Account {
         owner: proposer,
@@ -547,7 +648,7 @@ fn test_withdraw_deposit_from_pending_claim() {
     // Transfer deposit to proposer's subaccount
     let subaccount = claim_client.connect(proposer).get_claim_deposit_subaccount(
         proposer,
-        receiver,
+        coverage_id,
         claim_amount.clone(),
         pool_canister,
         claim_desc.clone(),
@@ -563,7 +664,13 @@ fn test_withdraw_deposit_from_pending_claim() {
     // Create claim
     let claim_id = claim_client
         .connect(proposer)
-        .add_claim(receiver, claim_amount, pool_canister, claim_desc)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            claim_amount,
+            pool_canister,
+            claim_desc,
+        )
         .expect("add_claim should succeed");
 
     let balance_after_claim = ledger_client
@@ -590,16 +697,21 @@ fn test_withdraw_deposit_from_pending_claim() {
 
 #[test]
 fn test_mark_as_spam_blocks_deposit_withdrawal() {
-    let (pic, claim_canister, pool_canister, owner, ledger_id) = setup();
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
 
     let proposer_bytes = [16u8; 29];
     let proposer = Principal::from_slice(&proposer_bytes);
-    let receiver_bytes = [17u8; 29];
-    let receiver = Principal::from_slice(&receiver_bytes);
 
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
     let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
 
+    // The owner holds the coverage and lets the proposer file claims for it
+    claim_client
+        .connect(owner)
+        .add_delegate(proposer)
+        .expect("add_delegate should succeed");
+
     // Fund the proposer
     let proposer_account = pool_canister::types::Account {
         owner: proposer,
@@ -623,7 +735,7 @@ fn test_mark_as_spam_blocks_deposit_withdrawal() {
     // Transfer deposit to proposer's subaccount
     let subaccount = claim_client.connect(proposer).get_claim_deposit_subaccount(
         proposer,
-        receiver,
+        coverage_id,
         claim_amount.clone(),
         pool_canister,
         claim_desc.clone(),
@@ -639,7 +751,13 @@ fn test_mark_as_spam_blocks_deposit_withdrawal() {
     // Create claim
     let claim_id = claim_client
         .connect(proposer)
-        .add_claim(receiver, claim_amount, pool_canister, claim_desc)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            claim_amount,
+            pool_canister,
+            claim_desc,
+        )
         .expect("add_claim should succeed");
 
     let balance_after_claim = ledger_client
@@ -648,8 +766,8 @@ fn test_mark_as_spam_blocks_deposit_withdrawal() {
 
     // Mark as spam
     claim_client
-        .connect(owner)
-        .mark_as_spam(claim_id)
+        .connect(approver())
+        .mark_as_spam(claim_id, "Duplicate of an earlier claim".to_string())
         .expect("mark_as_spam should succeed");
 
     // Verify claim is marked as spam
@@ -676,16 +794,21 @@ fn test_mark_as_spam_blocks_deposit_withdrawal() {
 
 #[test]
 fn test_can_mark_approved_claim_as_spam() {
-    let (pic, claim_canister, pool_canister, owner, ledger_id) = setup();
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
 
     let proposer_bytes = [18u8; 29];
     let proposer = Principal::from_slice(&proposer_bytes);
-    let receiver_bytes = [19u8; 29];
-    let receiver = Principal::from_slice(&receiver_bytes);
 
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
     let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
 
+    // The owner holds the coverage and lets the proposer file claims for it
+    claim_client
+        .connect(owner)
+        .add_delegate(proposer)
+        .expect("add_delegate should succeed");
+
     // Fund the proposer
     let proposer_account = pool_canister::types::Account {
         owner: proposer,
@@ -709,7 +832,7 @@ fn test_can_mark_approved_claim_as_spam() {
     // Transfer deposit to proposer's subaccount
     let subaccount = claim_client.connect(proposer).get_claim_deposit_subaccount(
         proposer,
-        receiver,
+        coverage_id,
         claim_amount.clone(),
         pool_canister,
         claim_desc.clone(),
@@ -725,18 +848,24 @@ fn test_can_mark_approved_claim_as_spam() {
     // Create and approve claim
     let claim_id = claim_client
         .connect(proposer)
-        .add_claim(receiver, claim_amount, pool_canister, claim_desc)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            claim_amount,
+            pool_canister,
+            claim_desc,
+        )
         .expect("add_claim should succeed");
 
     claim_client
-        .connect(owner)
-        .approve_claim(claim_id)
+        .connect(approver())
+        .approve_claim(claim_id, None, "Loss verified".to_string())
         .expect("approve_claim should succeed");
 
     // Mark approved claim as spam - should succeed now
     claim_client
-        .connect(owner)
-        .mark_as_spam(claim_id)
+        .connect(approver())
+        .mark_as_spam(claim_id, "Duplicate of an earlier claim".to_string())
         .expect("mark_as_spam should succeed for approved claims");
 
     // Verify claim is marked as spam
@@ -746,18 +875,23 @@ fn test_can_mark_approved_claim_as_spam() {
 
 #[test]
 fn test_only_approver_can_mark_as_spam() {
-    let (pic, claim_canister, pool_canister, owner, ledger_id) = setup();
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
 
     let proposer_bytes = [20u8; 29];
     let proposer = Principal::from_slice(&proposer_bytes);
-    let receiver_bytes = [21u8; 29];
-    let receiver = Principal::from_slice(&receiver_bytes);
     let non_approver_bytes = [22u8; 29];
     let non_approver = Principal::from_slice(&non_approver_bytes);
 
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
     let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
 
+    // The owner holds the coverage and lets the proposer file claims for it
+    claim_client
+        .connect(owner)
+        .add_delegate(proposer)
+        .expect("add_delegate should succeed");
+
     // Fund the proposer
     let proposer_account = pool_canister::types::Account {
         owner: proposer,
@@ -781,7 +915,7 @@ fn test_only_approver_can_mark_as_spam() {
     // Transfer deposit to proposer's subaccount
     let subaccount = claim_client.connect(proposer).get_claim_deposit_subaccount(
         proposer,
-        receiver,
+        coverage_id,
         claim_amount.clone(),
         pool_canister,
         claim_desc.clone(),
@@ -797,17 +931,25 @@ fn test_only_approver_can_mark_as_spam() {
     // Create claim
     let claim_id = claim_client
         .connect(proposer)
-        .add_claim(receiver, claim_amount, pool_canister, claim_desc)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            claim_amount,
+            pool_canister,
+            claim_desc,
+        )
         .expect("add_claim should succeed");
 
     // Non-approver tries to mark as spam - should fail
-    let mark_result = claim_client.connect(non_approver).mark_as_spam(claim_id);
+    let mark_result = claim_client
+        .connect(non_approver)
+        .mark_as_spam(claim_id, "Duplicate of an earlier claim".to_string());
     assert_eq!(mark_result, Err(ClaimError::NotApprover));
 
     // Approver (owner) marks as spam - should succeed
     claim_client
-        .connect(owner)
-        .mark_as_spam(claim_id)
+        .connect(approver())
+        .mark_as_spam(claim_id, "Duplicate of an earlier claim".to_string())
         .expect("mark_as_spam should succeed for approver");
 
     let claim_info = claim_client.connect(owner).get_claim(claim_id).unwrap();
@@ -815,45 +957,90 @@ fn test_only_approver_can_mark_as_spam() {
 }
 
 #[test]
-fn test_only_owner_can_set_claim_deposit() {
-    let (pic, claim_canister, _pool_canister, owner, _ledger_id) = setup();
+fn test_only_owner_can_set_deposit_schedule() {
+    let (pic, claim_canister, pool_canister, owner, _ledger_id, _coverage_id) = setup();
 
     let non_owner_bytes = [23u8; 29];
     let non_owner = Principal::from_slice(&non_owner_bytes);
 
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
 
-    // Non-owner tries to set claim deposit - should fail
+    // 1M base plus 1% of the claimed amount, between 1.5M and 5M
+    let schedule = DepositSchedule {
+        base: Nat::from(1_000_000u64),
+        bps: 100,
+        min: Nat::from(1_500_000u64),
+        max: Nat::from(5_000_000u64),
+    };
+
+    // Non-owner tries to set the deposit schedule - should fail
     let set_result = claim_client
         .connect(non_owner)
-        .set_claim_deposit(Nat::from(2_000_000u64));
+        .set_deposit_schedule(schedule.clone());
     assert_eq!(set_result, Err(ClaimError::InsufficientPermissions));
 
-    // Owner sets claim deposit - should succeed
+    // Bounds must be ordered
+    let invalid_result = claim_client
+        .connect(owner)
+        .set_deposit_schedule(DepositSchedule {
+            min: Nat::from(6_000_000u64),
+            ..schedule.clone()
+        });
+    assert_eq!(invalid_result, Err(ClaimError::InvalidDepositSchedule));
+
+    // A zero cap cannot be combined with a nonzero base or rate
+    let zero_cap_result = claim_client
+        .connect(owner)
+        .set_deposit_schedule(DepositSchedule {
+            min: Nat::from(0u64),
+            max: Nat::from(0u64),
+            ..schedule.clone()
+        });
+    assert_eq!(zero_cap_result, Err(ClaimError::InvalidDepositSchedule));
+
+    // Owner sets the deposit schedule - should succeed
     claim_client
         .connect(owner)
-        .set_claim_deposit(Nat::from(2_000_000u64))
-        .expect("set_claim_deposit should succeed for owner");
+        .set_deposit_schedule(schedule.clone())
+        .expect("set_deposit_schedule should succeed for owner");
+    assert_eq!(claim_client.connect(owner).get_deposit_schedule(), schedule);
+
+    // Quotes follow the schedule and respect its bounds
+    let small_quote = claim_client
+        .connect(owner)
+        .get_claim_deposit(pool_canister, Nat::from(10_000_000u64));
+    assert_eq!(small_quote, Nat::from(1_500_000u64));
+
+    let medium_quote = claim_client
+        .connect(owner)
+        .get_claim_deposit(pool_canister, Nat::from(200_000_000u64));
+    assert_eq!(medium_quote, Nat::from(3_000_000u64));
 
-    // Verify the deposit was updated
-    let new_deposit = claim_client.connect(owner).get_claim_deposit();
-    assert_eq!(new_deposit, Nat::from(2_000_000u64));
+    let large_quote = claim_client
+        .connect(owner)
+        .get_claim_deposit(pool_canister, Nat::from(1_000_000_000u64));
+    assert_eq!(large_quote, Nat::from(5_000_000u64));
 }
 
 #[test]
 fn test_only_proposer_can_withdraw_deposit() {
-    let (pic, claim_canister, pool_canister, owner, ledger_id) = setup();
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
 
     let proposer_bytes = [24u8; 29];
     let proposer = Principal::from_slice(&proposer_bytes);
-    let receiver_bytes = [25u8; 29];
-    let receiver = Principal::from_slice(&receiver_bytes);
     let other_bytes = [26u8; 29];
     let other = Principal::from_slice(&other_bytes);
 
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
     let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
 
+    // The owner holds the coverage and lets the proposer file claims for it
+    claim_client
+        .connect(owner)
+        .add_delegate(proposer)
+        .expect("add_delegate should succeed");
+
     // Fund the proposer
     let proposer_account = pool_canister::types::Account {
         owner: proposer,
@@ -877,7 +1064,7 @@ fn test_only_proposer_can_withdraw_deposit() {
     // Transfer deposit to proposer's subaccount
     let subaccount = claim_client.connect(proposer).get_claim_deposit_subaccount(
         proposer,
-        receiver,
+        coverage_id,
         claim_amount.clone(),
         pool_canister,
         claim_desc.clone(),
@@ -893,7 +1080,13 @@ fn test_only_proposer_can_withdraw_deposit() {
     // Create claim
     let claim_id = claim_client
         .connect(proposer)
-        .add_claim(receiver, claim_amount, pool_canister, claim_desc)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            claim_amount,
+            pool_canister,
+            claim_desc,
+        )
         .expect("add_claim should succeed");
 
     // Other user tries to withdraw - should fail
@@ -916,16 +1109,21 @@ fn test_only_proposer_can_withdraw_deposit() {
 
 #[test]
 fn test_can_withdraw_deposit_from_approved_claim() {
-    let (pic, claim_canister, pool_canister, owner, ledger_id) = setup();
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
 
     let proposer_bytes = [27u8; 29];
     let proposer = Principal::from_slice(&proposer_bytes);
-    let receiver_bytes = [28u8; 29];
-    let receiver = Principal::from_slice(&receiver_bytes);
 
     let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
     let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
 
+    // The owner holds the coverage and lets the proposer file claims for it
+    claim_client
+        .connect(owner)
+        .add_delegate(proposer)
+        .expect("add_delegate should succeed");
+
     // Fund the proposer
     let proposer_account = pool_canister::types::Account {
         owner: proposer,
@@ -949,7 +1147,7 @@ fn test_can_withdraw_deposit_from_approved_claim() {
     // Transfer deposit to proposer's subaccount
     let subaccount = claim_client.connect(proposer).get_claim_deposit_subaccount(
         proposer,
-        receiver,
+        coverage_id,
         claim_amount.clone(),
         pool_canister,
         claim_desc.clone(),
@@ -965,7 +1163,13 @@ fn test_can_withdraw_deposit_from_approved_claim() {
     // Create claim
     let claim_id = claim_client
         .connect(proposer)
-        .add_claim(receiver, claim_amount, pool_canister, claim_desc)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            claim_amount,
+            pool_canister,
+            claim_desc,
+        )
         .expect("add_claim should succeed");
 
     let balance_after_claim = ledger_client
@@ -974,8 +1178,8 @@ fn test_can_withdraw_deposit_from_approved_claim() {
 
     // Approve claim
     claim_client
-        .connect(owner)
-        .approve_claim(claim_id)
+        .connect(approver())
+        .approve_claim(claim_id, None, "Loss verified".to_string())
         .expect("approve_claim should succeed");
 
     // Try to withdraw before approval period expires - should fail
@@ -1019,3 +1223,2010 @@ fn test_can_withdraw_deposit_from_approved_claim() {
         "Second withdrawal should fail"
     );
 }
+
+#[test]
+fn test_executed_claim_is_recorded_by_pool_and_not_reconcilable() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+
+    let receiver_bytes = [29u8; 29];
+    let receiver = Principal::from_slice(&receiver_bytes);
+    let amount = Nat::from(1_000_000u64);
+    let desc = String::from("Reconciliation test");
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    // Payouts for the owner's coverage go to the receiver
+    claim_client
+        .connect(owner)
+        .set_payout_account(Some(receiver))
+        .expect("set_payout_account should succeed");
+    let pool_client = PoolCanisterClient::new(&pic, pool_canister);
+
+    let deposit_amount = Nat::from(1_000_000u64);
+    let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+        owner,
+        coverage_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    transfer_to_subaccount(
+        &mut ledger_client,
+        owner,
+        claim_canister,
+        subaccount,
+        deposit_amount,
+    );
+
+    let claim_id = claim_client
+        .connect(owner)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            amount.clone(),
+            pool_canister,
+            desc,
+        )
+        .expect("add_claim should succeed");
+
+    // Only claims stuck in Executing can be reconciled
+    let pending_result = claim_client.connect(owner).reconcile_claim(claim_id);
+    assert_eq!(pending_result, Err(ClaimError::NotExecuting));
+
+    claim_client
+        .connect(approver())
+        .approve_claim(claim_id, None, "Loss verified".to_string())
+        .expect("approve_claim should succeed");
+
+    pic.advance_time(Duration::from_nanos(EXECUTION_TIMEOUT_NANOS));
+
+    claim_client
+        .connect(receiver)
+        .execute_claim(claim_id)
+        .expect("execute_claim should succeed");
+
+    // The pool keeps the slash keyed by the claim id
+    let record = pool_client
+        .get_slash_record(claim_id)
+        .expect("pool should record the slash for the claim");
+    assert_eq!(record.status, pool_canister::SlashStatus::Completed);
+    assert_eq!(record.amount, amount);
+    assert_eq!(record.receiver, receiver);
+
+    // Only the owner may trigger reconciliation
+    let non_owner_result = claim_client.connect(receiver).reconcile_claim(claim_id);
+    assert_eq!(non_owner_result, Err(ClaimError::InsufficientPermissions));
+
+    let executed_result = claim_client.connect(owner).reconcile_claim(claim_id);
+    assert_eq!(executed_result, Err(ClaimError::NotExecuting));
+}
+
+#[test]
+fn test_claim_evidence_and_comments() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+
+    let approver_bytes = [30u8; 29];
+    let approver = Principal::from_slice(&approver_bytes);
+    let outsider_bytes = [31u8; 29];
+    let outsider = Principal::from_slice(&outsider_bytes);
+    let amount = Nat::from(1_000_000u64);
+    let desc = String::from("Evidence test");
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    claim_client
+        .connect(owner)
+        .add_approver(approver)
+        .expect("add_approver should succeed");
+
+    let deposit_amount = Nat::from(1_000_000u64);
+    let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+        owner,
+        coverage_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    transfer_to_subaccount(
+        &mut ledger_client,
+        owner,
+        claim_canister,
+        subaccount,
+        deposit_amount,
+    );
+
+    let claim_id = claim_client
+        .connect(owner)
+        .add_claim(coverage_id, incident_id, amount, pool_canister, desc)
+        .expect("add_claim should succeed");
+
+    // Proposer attaches evidence
+    claim_client
+        .connect(owner)
+        .add_claim_evidence(
+            claim_id,
+            vec![7u8; 32],
+            "application/pdf".to_string(),
+            "ipfs://incident-report".to_string(),
+        )
+        .expect("proposer should be able to add evidence");
+
+    // Evidence must carry a 32-byte content hash
+    let invalid_result = claim_client.connect(owner).add_claim_evidence(
+        claim_id,
+        vec![7u8; 5],
+        "application/pdf".to_string(),
+        "ipfs://incident-report".to_string(),
+    );
+    assert_eq!(invalid_result, Err(ClaimError::InvalidEvidence));
+
+    // Approvers can join the discussion, outsiders cannot
+    claim_client
+        .connect(approver)
+        .add_claim_comment(claim_id, "Please attach the transaction hash".to_string())
+        .expect("approver should be able to comment");
+
+    let outsider_result = claim_client
+        .connect(outsider)
+        .add_claim_comment(claim_id, "Spam".to_string());
+    assert_eq!(outsider_result, Err(ClaimError::InsufficientPermissions));
+
+    let evidence = claim_client.connect(outsider).get_claim_evidence(claim_id);
+    assert_eq!(evidence.len(), 1);
+    assert_eq!(evidence[0].submitter, owner);
+    assert_eq!(evidence[0].uri, "ipfs://incident-report");
+
+    let comments = claim_client.connect(outsider).get_claim_comments(claim_id);
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].author, approver);
+
+    // Once the claim leaves Pending the record is frozen
+    claim_client
+        .connect(approver)
+        .approve_claim(claim_id, None, "Loss verified".to_string())
+        .expect("approve_claim should succeed");
+
+    let late_result = claim_client
+        .connect(owner)
+        .add_claim_comment(claim_id, "One more thing".to_string());
+    assert_eq!(late_result, Err(ClaimError::ClaimNotPending));
+}
+
+#[test]
+fn test_decisions_are_recorded_in_audit_log() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+
+    let reviewer_bytes = [33u8; 29];
+    let reviewer = Principal::from_slice(&reviewer_bytes);
+    let receiver_bytes = [34u8; 29];
+    let receiver = Principal::from_slice(&receiver_bytes);
+    let amount = Nat::from(1_000_000u64);
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    claim_client
+        .connect(owner)
+        .add_approver(reviewer)
+        .expect("add_approver should succeed");
+
+    let mut claim_ids = vec![];
+    for desc in ["First audited claim", "Second audited claim"] {
+        let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+            owner,
+            coverage_id,
+            amount.clone(),
+            pool_canister,
+            desc.to_string(),
+        );
+        transfer_to_subaccount(
+            &mut ledger_client,
+            owner,
+            claim_canister,
+            subaccount,
+            Nat::from(1_000_000u64),
+        );
+        let claim_id = claim_client
+            .connect(owner)
+            .add_claim(
+                coverage_id,
+                incident_id,
+                amount.clone(),
+                pool_canister,
+                desc.to_string(),
+            )
+            .expect("add_claim should succeed");
+        claim_ids.push(claim_id);
+    }
+
+    // A rationale is mandatory
+    let empty_rationale = claim_client
+        .connect(reviewer)
+        .reject_claim(claim_ids[0], "".to_string());
+    assert_eq!(empty_rationale, Err(ClaimError::InvalidRationale));
+
+    claim_client
+        .connect(reviewer)
+        .reject_claim(claim_ids[0], "Incident outside of coverage".to_string())
+        .expect("reject_claim should succeed");
+    claim_client
+        .connect(approver())
+        .approve_claim(claim_ids[1], None, "Loss verified".to_string())
+        .expect("approve_claim should succeed");
+    claim_client
+        .connect(reviewer)
+        .mark_as_spam(claim_ids[1], "Fabricated evidence".to_string())
+        .expect("mark_as_spam should succeed");
+
+    let rejected_info = claim_client.connect(owner).get_claim(claim_ids[0]).unwrap();
+    assert_eq!(rejected_info.status, ClaimStatus::Rejected);
+
+    // Rejected claims cannot be rejected twice
+    let second_reject = claim_client
+        .connect(reviewer)
+        .reject_claim(claim_ids[0], "Still outside of coverage".to_string());
+    assert_eq!(second_reject, Err(ClaimError::ClaimNotPending));
+
+    // Rejected and spam claims can only be revisited through an appeal
+    for claim_id in claim_ids.iter().take(2) {
+        let approve_result = claim_client.connect(approver()).approve_claim(
+            *claim_id,
+            None,
+            "Reconsidered".to_string(),
+        );
+        assert_eq!(approve_result, Err(ClaimError::ClaimNotPending));
+    }
+
+    let claim_log = claim_client
+        .connect(receiver)
+        .get_claim_decisions(claim_ids[1]);
+    assert_eq!(claim_log.len(), 2);
+    assert_eq!(claim_log[0].kind, DecisionKind::Approve);
+    assert_eq!(claim_log[0].actor, approver());
+    assert_eq!(claim_log[1].kind, DecisionKind::Spam);
+    assert_eq!(claim_log[1].actor, reviewer);
+    assert_eq!(claim_log[1].rationale, "Fabricated evidence");
+
+    let approver_log = claim_client
+        .connect(receiver)
+        .get_approver_decisions(reviewer);
+    assert_eq!(approver_log.len(), 2);
+    assert_eq!(approver_log[0].claim_id, claim_ids[0]);
+    assert_eq!(approver_log[0].kind, DecisionKind::Reject);
+    assert_eq!(approver_log[1].claim_id, claim_ids[1]);
+}
+
+#[test]
+fn test_conflict_of_interest_rules() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+
+    let receiver_bytes = [35u8; 29];
+    let receiver = Principal::from_slice(&receiver_bytes);
+    let new_approver_bytes = [36u8; 29];
+    let new_approver = Principal::from_slice(&new_approver_bytes);
+    let amount = Nat::from(1_000_000u64);
+    let desc = String::from("Conflict of interest test");
+    let cooling_off_period = 3 * 24 * 60 * 60 * 1_000_000_000u64; // 3 days in nanoseconds
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    // Payouts for the owner's coverage go to the receiver
+    claim_client
+        .connect(owner)
+        .set_payout_account(Some(receiver))
+        .expect("set_payout_account should succeed");
+
+    let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+        owner,
+        coverage_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    transfer_to_subaccount(
+        &mut ledger_client,
+        owner,
+        claim_canister,
+        subaccount,
+        Nat::from(1_000_000u64),
+    );
+
+    let claim_id = claim_client
+        .connect(owner)
+        .add_claim(coverage_id, incident_id, amount, pool_canister, desc)
+        .expect("add_claim should succeed");
+
+    // The owner filed the claim and cannot decide on it
+    let self_approval =
+        claim_client
+            .connect(owner)
+            .approve_claim(claim_id, None, "Loss verified".to_string());
+    assert_eq!(
+        self_approval,
+        Err(ClaimError::ApproverIneligible(
+            IneligibilityReason::Proposer
+        ))
+    );
+    let self_rejection = claim_client
+        .connect(owner)
+        .reject_claim(claim_id, "Not covered".to_string());
+    assert_eq!(
+        self_rejection,
+        Err(ClaimError::ApproverIneligible(
+            IneligibilityReason::Proposer
+        ))
+    );
+    let self_spam = claim_client
+        .connect(owner)
+        .mark_as_spam(claim_id, "Duplicate".to_string());
+    assert_eq!(
+        self_spam,
+        Err(ClaimError::ApproverIneligible(
+            IneligibilityReason::Proposer
+        ))
+    );
+
+    // The receiver is ineligible as well once they become an approver
+    claim_client
+        .connect(owner)
+        .add_approver(receiver)
+        .expect("add_approver should succeed");
+    let receiver_reasons = claim_client
+        .connect(owner)
+        .get_approver_ineligibility(claim_id, receiver)
+        .expect("claim should exist");
+    assert_eq!(receiver_reasons, vec![IneligibilityReason::Receiver]);
+
+    // Newly added approvers have to wait for the cooling-off period
+    claim_client
+        .connect(owner)
+        .set_conflict_rules(ConflictRules {
+            exclude_proposer: true,
+            exclude_receiver: true,
+            cooling_off_period,
+        })
+        .expect("set_conflict_rules should succeed for owner");
+
+    // Cooling-off periods are bounded
+    let excessive_rules = claim_client
+        .connect(owner)
+        .set_conflict_rules(ConflictRules {
+            exclude_proposer: true,
+            exclude_receiver: true,
+            cooling_off_period: MAX_COOLING_OFF_PERIOD + 1,
+        });
+    assert_eq!(excessive_rules, Err(ClaimError::InvalidConfig));
+
+    // The rules decide claims in flight, so they only apply after the delay
+    assert_eq!(
+        claim_client
+            .connect(owner)
+            .get_conflict_rules()
+            .cooling_off_period,
+        0
+    );
+    pic.advance_time(Duration::from_nanos(CONFIG_CHANGE_DELAY));
+    assert_eq!(
+        claim_client
+            .connect(owner)
+            .get_conflict_rules()
+            .cooling_off_period,
+        cooling_off_period
+    );
+
+    claim_client
+        .connect(owner)
+        .add_approver(new_approver)
+        .expect("add_approver should succeed");
+
+    let new_approver_reasons = claim_client
+        .connect(owner)
+        .get_approver_ineligibility(claim_id, new_approver)
+        .expect("claim should exist");
+    assert!(matches!(
+        new_approver_reasons.as_slice(),
+        [IneligibilityReason::CoolingOff { .. }]
+    ));
+
+    // Dropping the global role keeps the cooling-off while a pool role remains
+    claim_client
+        .connect(owner)
+        .register_pool(pool_canister, None, None, None, vec![new_approver])
+        .expect("register_pool should succeed for owner");
+    pic.advance_time(Duration::from_nanos(CONFIG_CHANGE_DELAY));
+    claim_client
+        .connect(owner)
+        .remove_approver(new_approver)
+        .expect("remove_approver should succeed");
+
+    let pool_approver_reasons = claim_client
+        .connect(owner)
+        .get_approver_ineligibility(claim_id, new_approver)
+        .expect("claim should exist");
+    assert!(matches!(
+        pool_approver_reasons.as_slice(),
+        [IneligibilityReason::CoolingOff { .. }]
+    ));
+
+    let early_approval = claim_client.connect(new_approver).approve_claim(
+        claim_id,
+        None,
+        "Loss verified".to_string(),
+    );
+    assert!(matches!(
+        early_approval,
+        Err(ClaimError::ApproverIneligible(
+            IneligibilityReason::CoolingOff { .. }
+        ))
+    ));
+
+    pic.advance_time(Duration::from_nanos(
+        cooling_off_period - CONFIG_CHANGE_DELAY,
+    ));
+
+    let eligible_reasons = claim_client
+        .connect(owner)
+        .get_approver_ineligibility(claim_id, new_approver)
+        .expect("claim should exist");
+    assert!(eligible_reasons.is_empty());
+
+    claim_client
+        .connect(new_approver)
+        .approve_claim(claim_id, None, "Loss verified".to_string())
+        .expect("approve_claim should succeed after cooling-off");
+}
+
+#[test]
+fn test_claims_require_registered_pool() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+
+    let receiver_bytes = [37u8; 29];
+    let receiver = Principal::from_slice(&receiver_bytes);
+    let unknown_pool_bytes = [38u8; 29];
+    let unknown_pool = Principal::from_slice(&unknown_pool_bytes);
+    let pool_approver_bytes = [39u8; 29];
+    let pool_approver = Principal::from_slice(&pool_approver_bytes);
+    let amount = Nat::from(1_000_000u64);
+    let desc = String::from("Registered pool test");
+    let pool_deposit = Nat::from(2_000_000u64);
+    let pool_schedule = DepositSchedule {
+        base: pool_deposit.clone(),
+        bps: 0,
+        min: pool_deposit.clone(),
+        max: pool_deposit.clone(),
+    };
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    // Claims against unknown pools are rejected
+    let unknown_result = claim_client.connect(owner).add_claim(
+        coverage_id,
+        incident_id,
+        amount.clone(),
+        unknown_pool,
+        desc.clone(),
+    );
+    assert_eq!(unknown_result, Err(ClaimError::PoolNotRegistered));
+
+    // Only the owner manages the registry
+    let register_result = claim_client.connect(receiver).register_pool(
+        pool_canister,
+        None,
+        None,
+        Some(pool_schedule.clone()),
+        vec![pool_approver],
+    );
+    assert_eq!(register_result, Err(ClaimError::InsufficientPermissions));
+
+    claim_client
+        .connect(owner)
+        .register_pool(
+            pool_canister,
+            None,
+            None,
+            Some(pool_schedule.clone()),
+            vec![pool_approver],
+        )
+        .expect("register_pool should succeed for owner");
+
+    // Changes to a registered pool only apply after the delay
+    let pool = claim_client
+        .connect(owner)
+        .get_registered_pool(pool_canister)
+        .unwrap();
+    assert_eq!(pool.deposit_schedule, None);
+    assert!(pool.approvers.is_empty());
+    pic.advance_time(Duration::from_nanos(CONFIG_CHANGE_DELAY));
+
+    let pools = claim_client.connect(owner).get_registered_pools();
+    assert_eq!(pools.len(), 1);
+    assert_eq!(pools[0].pool_canister_id, pool_canister);
+    assert_eq!(pools[0].deposit_schedule, Some(pool_schedule));
+    assert_eq!(pools[0].approvers, vec![pool_approver]);
+
+    // The per-pool deposit applies instead of the global one
+    let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+        owner,
+        coverage_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    transfer_to_subaccount(
+        &mut ledger_client,
+        owner,
+        claim_canister,
+        subaccount,
+        Nat::from(1_000_000u64),
+    );
+    let insufficient_result = claim_client.connect(owner).add_claim(
+        coverage_id,
+        incident_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    assert_eq!(insufficient_result, Err(ClaimError::InsufficientDeposit));
+
+    transfer_to_subaccount(
+        &mut ledger_client,
+        owner,
+        claim_canister,
+        subaccount,
+        Nat::from(1_000_000u64),
+    );
+    let claim_id = claim_client
+        .connect(owner)
+        .add_claim(coverage_id, incident_id, amount, pool_canister, desc)
+        .expect("add_claim should succeed");
+
+    let claim_info = claim_client.connect(owner).get_claim(claim_id).unwrap();
+    assert_eq!(claim_info.deposit_amount, pool_deposit);
+
+    // Only the pool's own approvers decide its claims
+    let global_approval =
+        claim_client
+            .connect(approver())
+            .approve_claim(claim_id, None, "Loss verified".to_string());
+    assert_eq!(global_approval, Err(ClaimError::NotApprover));
+
+    claim_client
+        .connect(pool_approver)
+        .approve_claim(claim_id, None, "Loss verified".to_string())
+        .expect("approve_claim should succeed for pool approver");
+
+    // Unregistered pools no longer accept new claims
+    claim_client
+        .connect(owner)
+        .unregister_pool(pool_canister)
+        .expect("unregister_pool should succeed for owner");
+    assert!(claim_client
+        .connect(owner)
+        .get_registered_pool(pool_canister)
+        .is_none());
+
+    // Nor do they pay out approved ones
+    pic.advance_time(Duration::from_nanos(EXECUTION_TIMEOUT_NANOS));
+    let execute_result = claim_client.connect(owner).execute_claim(claim_id);
+    assert_eq!(execute_result, Err(ClaimError::PoolNotRegistered));
+}
+
+#[test]
+fn test_partial_approval_accept_and_dispute() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+
+    let receiver_bytes = [40u8; 29];
+    let receiver = Principal::from_slice(&receiver_bytes);
+    let amount = Nat::from(1_000_000u64);
+    let approved_amount = Nat::from(600_000u64);
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+    let pool_client = PoolCanisterClient::new(&pic, pool_canister);
+
+    let mut claim_ids = vec![];
+    for desc in ["Partial approval accepted", "Partial approval disputed"] {
+        let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+            owner,
+            coverage_id,
+            amount.clone(),
+            pool_canister,
+            desc.to_string(),
+        );
+        transfer_to_subaccount(
+            &mut ledger_client,
+            owner,
+            claim_canister,
+            subaccount,
+            Nat::from(1_000_000u64),
+        );
+        let claim_id = claim_client
+            .connect(owner)
+            .add_claim(
+                coverage_id,
+                incident_id,
+                amount.clone(),
+                pool_canister,
+                desc.to_string(),
+            )
+            .expect("add_claim should succeed");
+        claim_ids.push(claim_id);
+    }
+
+    // Approved amounts must be positive and cannot exceed the requested amount
+    let excessive_result = claim_client.connect(approver()).approve_claim(
+        claim_ids[0],
+        Some(Nat::from(2_000_000u64)),
+        "Loss verified".to_string(),
+    );
+    assert_eq!(excessive_result, Err(ClaimError::InvalidApprovedAmount));
+
+    for claim_id in &claim_ids {
+        claim_client
+            .connect(approver())
+            .approve_claim(
+                *claim_id,
+                Some(approved_amount.clone()),
+                "Deductible applied".to_string(),
+            )
+            .expect("approve_claim should succeed");
+    }
+
+    let claim_info = claim_client.connect(owner).get_claim(claim_ids[0]).unwrap();
+    assert_eq!(claim_info.status, ClaimStatus::PartiallyApproved);
+    assert_eq!(claim_info.amount, amount);
+    assert_eq!(claim_info.approved_amount, Some(approved_amount.clone()));
+
+    // A partial approval is not executable until the proposer accepts it
+    pic.advance_time(Duration::from_nanos(EXECUTION_TIMEOUT_NANOS));
+    let unaccepted_result = claim_client.connect(receiver).execute_claim(claim_ids[0]);
+    assert_eq!(unaccepted_result, Err(ClaimError::NotApproved));
+
+    let non_proposer_result = claim_client
+        .connect(receiver)
+        .accept_partial_approval(claim_ids[0]);
+    assert_eq!(non_proposer_result, Err(ClaimError::NotProposer));
+
+    claim_client
+        .connect(owner)
+        .accept_partial_approval(claim_ids[0])
+        .expect("accept_partial_approval should succeed");
+
+    // The execution timelock restarts on acceptance
+    let early_result = claim_client.connect(receiver).execute_claim(claim_ids[0]);
+    assert_eq!(early_result, Err(ClaimError::ExecutionTimeoutNotExpired));
+
+    pic.advance_time(Duration::from_nanos(EXECUTION_TIMEOUT_NANOS));
+    claim_client
+        .connect(receiver)
+        .execute_claim(claim_ids[0])
+        .expect("execute_claim should succeed");
+
+    let record = pool_client
+        .get_slash_record(claim_ids[0])
+        .expect("pool should record the slash for the claim");
+    assert_eq!(record.amount, approved_amount);
+
+    // Disputing leaves the claim unexecutable and records the proposer's rationale
+    claim_client
+        .connect(owner)
+        .dispute_partial_approval(claim_ids[1], "Deductible does not apply".to_string())
+        .expect("dispute_partial_approval should succeed");
+
+    let disputed_info = claim_client.connect(owner).get_claim(claim_ids[1]).unwrap();
+    assert_eq!(disputed_info.status, ClaimStatus::Disputed);
+
+    let accept_after_dispute = claim_client
+        .connect(owner)
+        .accept_partial_approval(claim_ids[1]);
+    assert_eq!(accept_after_dispute, Err(ClaimError::NotPartiallyApproved));
+
+    let decisions = claim_client
+        .connect(owner)
+        .get_claim_decisions(claim_ids[1]);
+    assert_eq!(decisions.len(), 2);
+    assert_eq!(decisions[1].kind, DecisionKind::Dispute);
+    assert_eq!(decisions[1].actor, owner);
+}
+
+#[test]
+fn test_appeal_upheld_returns_deposit_and_dismissed_forfeits_it() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+
+    let receiver_bytes = [41u8; 29];
+    let receiver = Principal::from_slice(&receiver_bytes);
+    let member_a_bytes = [42u8; 29];
+    let member_a = Principal::from_slice(&member_a_bytes);
+    let member_b_bytes = [43u8; 29];
+    let member_b = Principal::from_slice(&member_b_bytes);
+    let amount = Nat::from(1_000_000u64);
+    let appeal_deposit = Nat::from(500_000u64);
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    // The appeal deposit has to cover the refund transfer fee
+    let dust_deposit = claim_client
+        .connect(owner)
+        .set_appeal_deposit(TRANSFER_FEE.clone());
+    assert_eq!(dust_deposit, Err(ClaimError::InvalidConfig));
+
+    claim_client
+        .connect(owner)
+        .set_appeal_deposit(appeal_deposit.clone())
+        .expect("set_appeal_deposit should succeed for owner");
+    claim_client
+        .connect(owner)
+        .set_appeal_quorum(2)
+        .expect("set_appeal_quorum should succeed for owner");
+    pic.advance_time(Duration::from_nanos(CONFIG_CHANGE_DELAY));
+    assert_eq!(claim_client.connect(owner).get_appeal_quorum(), 2);
+    for member in [member_a, member_b, approver()] {
+        claim_client
+            .connect(owner)
+            .add_appeal_committee_member(member)
+            .expect("add_appeal_committee_member should succeed for owner");
+    }
+
+    let mut claim_ids = vec![];
+    for desc in ["Appeal upheld", "Appeal dismissed"] {
+        let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+            owner,
+            coverage_id,
+            amount.clone(),
+            pool_canister,
+            desc.to_string(),
+        );
+        transfer_to_subaccount(
+            &mut ledger_client,
+            owner,
+            claim_canister,
+            subaccount,
+            Nat::from(1_000_000u64),
+        );
+        let claim_id = claim_client
+            .connect(owner)
+            .add_claim(
+                coverage_id,
+                incident_id,
+                amount.clone(),
+                pool_canister,
+                desc.to_string(),
+            )
+            .expect("add_claim should succeed");
+        claim_ids.push(claim_id);
+    }
+
+    // Pending claims cannot be appealed
+    let pending_result = claim_client
+        .connect(owner)
+        .open_appeal(claim_ids[0], "Please review again".to_string());
+    assert_eq!(pending_result, Err(ClaimError::NotAppealable));
+
+    claim_client
+        .connect(approver())
+        .reject_claim(claim_ids[0], "Not covered".to_string())
+        .expect("reject_claim should succeed");
+    claim_client
+        .connect(approver())
+        .mark_as_spam(claim_ids[1], "Duplicate of an earlier claim".to_string())
+        .expect("mark_as_spam should succeed");
+
+    // The appeal deposit has to be posted first
+    let unfunded_result = claim_client
+        .connect(owner)
+        .open_appeal(claim_ids[0], "Please review again".to_string());
+    assert_eq!(unfunded_result, Err(ClaimError::InsufficientDeposit));
+
+    for claim_id in &claim_ids {
+        let subaccount = claim_client
+            .connect(owner)
+            .get_appeal_deposit_subaccount(*claim_id);
+        transfer_to_subaccount(
+            &mut ledger_client,
+            owner,
+            claim_canister,
+            subaccount,
+            appeal_deposit.clone(),
+        );
+        claim_client
+            .connect(owner)
+            .open_appeal(*claim_id, "Please review again".to_string())
+            .expect("open_appeal should succeed");
+    }
+
+    let claim_info = claim_client.connect(owner).get_claim(claim_ids[0]).unwrap();
+    assert_eq!(claim_info.status, ClaimStatus::Appealed);
+
+    let second_appeal = claim_client
+        .connect(owner)
+        .open_appeal(claim_ids[0], "Please review again".to_string());
+    assert_eq!(second_appeal, Err(ClaimError::AppealAlreadyOpened));
+
+    let withdraw_result = claim_client.connect(owner).withdraw_deposit(claim_ids[0]);
+    assert_eq!(withdraw_result, Err(ClaimError::AppealInProgress));
+
+    // Only committee members who did not take the original decision may vote
+    let outsider_vote = claim_client.connect(receiver).vote_on_appeal(
+        claim_ids[0],
+        true,
+        "Loss verified".to_string(),
+    );
+    assert_eq!(outsider_vote, Err(ClaimError::NotAppealCommitteeMember));
+
+    let original_decider_vote = claim_client.connect(approver()).vote_on_appeal(
+        claim_ids[0],
+        true,
+        "Loss verified".to_string(),
+    );
+    assert_eq!(
+        original_decider_vote,
+        Err(ClaimError::ApproverIneligible(
+            IneligibilityReason::OriginalDecisionMaker
+        ))
+    );
+
+    claim_client
+        .connect(member_a)
+        .vote_on_appeal(claim_ids[0], true, "Loss verified".to_string())
+        .expect("vote_on_appeal should succeed");
+
+    let double_vote = claim_client.connect(member_a).vote_on_appeal(
+        claim_ids[0],
+        true,
+        "Loss verified".to_string(),
+    );
+    assert_eq!(double_vote, Err(ClaimError::AlreadyVoted));
+
+    // The appeal resolves once the quorum is reached
+    let appeal = claim_client
+        .connect(owner)
+        .get_appeal(claim_ids[0])
+        .unwrap();
+    assert_eq!(appeal.status, AppealStatus::Open);
+
+    claim_client
+        .connect(member_b)
+        .vote_on_appeal(claim_ids[0], true, "Loss verified".to_string())
+        .expect("vote_on_appeal should succeed");
+
+    let appeal = claim_client
+        .connect(owner)
+        .get_appeal(claim_ids[0])
+        .unwrap();
+    assert_eq!(appeal.status, AppealStatus::Upheld);
+    let claim_info = claim_client.connect(owner).get_claim(claim_ids[0]).unwrap();
+    assert_eq!(claim_info.status, ClaimStatus::Approved);
+    assert_eq!(claim_info.approved_amount, Some(amount.clone()));
+
+    let owner_account = pool_canister::types::Account {
+        owner,
+        subaccount: None,
+    };
+    let balance_before = ledger_client
+        .connect(owner)
+        .icrc1_balance_of(owner_account.clone());
+    claim_client
+        .connect(owner)
+        .withdraw_appeal_deposit(claim_ids[0])
+        .expect("withdraw_appeal_deposit should succeed");
+    let balance_after = ledger_client.connect(owner).icrc1_balance_of(owner_account);
+    assert_eq!(
+        balance_after - balance_before,
+        appeal_deposit.clone() - TRANSFER_FEE.clone()
+    );
+
+    // A dismissed appeal restores the original decision and keeps the deposit
+    for member in [member_a, member_b] {
+        claim_client
+            .connect(member)
+            .vote_on_appeal(claim_ids[1], false, "Claim is a duplicate".to_string())
+            .expect("vote_on_appeal should succeed");
+    }
+
+    let appeal = claim_client
+        .connect(owner)
+        .get_appeal(claim_ids[1])
+        .unwrap();
+    assert_eq!(appeal.status, AppealStatus::Dismissed);
+    let claim_info = claim_client.connect(owner).get_claim(claim_ids[1]).unwrap();
+    assert_eq!(claim_info.status, ClaimStatus::Spam);
+
+    let forfeited_result = claim_client
+        .connect(owner)
+        .withdraw_appeal_deposit(claim_ids[1]);
+    assert_eq!(forfeited_result, Err(ClaimError::AppealNotUpheld));
+
+    // The owner collects forfeited deposits, but only of dismissed appeals
+    let outsider_collect = claim_client
+        .connect(receiver)
+        .collect_forfeited_appeal_deposit(claim_ids[1]);
+    assert_eq!(outsider_collect, Err(ClaimError::InsufficientPermissions));
+
+    let upheld_collect = claim_client
+        .connect(owner)
+        .collect_forfeited_appeal_deposit(claim_ids[0]);
+    assert_eq!(upheld_collect, Err(ClaimError::AppealNotDismissed));
+
+    let owner_account = pool_canister::types::Account {
+        owner,
+        subaccount: None,
+    };
+    let balance_before = ledger_client
+        .connect(owner)
+        .icrc1_balance_of(owner_account.clone());
+    claim_client
+        .connect(owner)
+        .collect_forfeited_appeal_deposit(claim_ids[1])
+        .expect("collect_forfeited_appeal_deposit should succeed for owner");
+    let balance_after = ledger_client.connect(owner).icrc1_balance_of(owner_account);
+    assert_eq!(
+        balance_after - balance_before,
+        appeal_deposit - TRANSFER_FEE.clone()
+    );
+
+    let second_collect = claim_client
+        .connect(owner)
+        .collect_forfeited_appeal_deposit(claim_ids[1]);
+    assert_eq!(second_collect, Err(ClaimError::NoDepositToWithdraw));
+}
+
+#[test]
+fn test_unresolved_appeal_expires_and_refunds_deposit() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+
+    let member_bytes = [47u8; 29];
+    let member = Principal::from_slice(&member_bytes);
+    let amount = Nat::from(1_000_000u64);
+    let appeal_deposit = Nat::from(500_000u64);
+    let desc = String::from("Appeal expires");
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    claim_client
+        .connect(owner)
+        .set_appeal_deposit(appeal_deposit.clone())
+        .expect("set_appeal_deposit should succeed for owner");
+    claim_client
+        .connect(owner)
+        .add_appeal_committee_member(member)
+        .expect("add_appeal_committee_member should succeed for owner");
+
+    let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+        owner,
+        coverage_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    transfer_to_subaccount(
+        &mut ledger_client,
+        owner,
+        claim_canister,
+        subaccount,
+        Nat::from(1_000_000u64),
+    );
+    let claim_id = claim_client
+        .connect(owner)
+        .add_claim(coverage_id, incident_id, amount, pool_canister, desc)
+        .expect("add_claim should succeed");
+
+    claim_client
+        .connect(approver())
+        .reject_claim(claim_id, "Not covered".to_string())
+        .expect("reject_claim should succeed");
+
+    let appeal_subaccount = claim_client
+        .connect(owner)
+        .get_appeal_deposit_subaccount(claim_id);
+    transfer_to_subaccount(
+        &mut ledger_client,
+        owner,
+        claim_canister,
+        appeal_subaccount,
+        appeal_deposit.clone(),
+    );
+    claim_client
+        .connect(owner)
+        .open_appeal(claim_id, "Please review again".to_string())
+        .expect("open_appeal should succeed");
+
+    let early_expiry = claim_client.connect(owner).expire_appeal(claim_id);
+    assert_eq!(early_expiry, Err(ClaimError::AppealVotingPeriodNotExpired));
+
+    pic.advance_time(Duration::from_nanos(APPEAL_VOTING_PERIOD));
+
+    // Votes are no longer accepted once the voting period is over
+    let late_vote =
+        claim_client
+            .connect(member)
+            .vote_on_appeal(claim_id, true, "Loss verified".to_string());
+    assert_eq!(late_vote, Err(ClaimError::AppealExpired));
+
+    // The expired appeal restores the decision and returns both deposits
+    claim_client
+        .connect(owner)
+        .withdraw_deposit(claim_id)
+        .expect("withdraw_deposit should succeed once the appeal expired");
+
+    let appeal = claim_client.connect(owner).get_appeal(claim_id).unwrap();
+    assert_eq!(appeal.status, AppealStatus::Expired);
+    let claim_info = claim_client.connect(owner).get_claim(claim_id).unwrap();
+    assert_eq!(claim_info.status, ClaimStatus::Rejected);
+
+    let owner_account = pool_canister::types::Account {
+        owner,
+        subaccount: None,
+    };
+    let balance_before = ledger_client
+        .connect(owner)
+        .icrc1_balance_of(owner_account.clone());
+    claim_client
+        .connect(owner)
+        .withdraw_appeal_deposit(claim_id)
+        .expect("withdraw_appeal_deposit should succeed for expired appeal");
+    let balance_after = ledger_client.connect(owner).icrc1_balance_of(owner_account);
+    assert_eq!(
+        balance_after - balance_before,
+        appeal_deposit - TRANSFER_FEE.clone()
+    );
+}
+
+#[test]
+fn test_two_step_ownership_transfer() {
+    let (pic, claim_canister, _pool_canister, owner, _ledger_id, _coverage_id) = setup();
+
+    let new_owner_bytes = [44u8; 29];
+    let new_owner = Principal::from_slice(&new_owner_bytes);
+    let outsider_bytes = [45u8; 29];
+    let outsider = Principal::from_slice(&outsider_bytes);
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+
+    let non_owner_result = claim_client.connect(outsider).propose_owner(outsider);
+    assert_eq!(non_owner_result, Err(ClaimError::InsufficientPermissions));
+
+    claim_client
+        .connect(owner)
+        .propose_owner(new_owner)
+        .expect("propose_owner should succeed for owner");
+
+    // Ownership only moves once the proposed owner accepts it
+    let config = claim_client.connect(owner).get_config();
+    assert_eq!(config.owner, owner);
+    assert_eq!(config.pending_owner, Some(new_owner));
+
+    let outsider_accept = claim_client.connect(outsider).accept_ownership();
+    assert_eq!(outsider_accept, Err(ClaimError::NotPendingOwner));
+
+    claim_client
+        .connect(new_owner)
+        .accept_ownership()
+        .expect("accept_ownership should succeed for pending owner");
+
+    assert_eq!(claim_client.connect(owner).get_owner(), new_owner);
+    assert_eq!(claim_client.connect(owner).get_config().pending_owner, None);
+
+    let old_owner_result = claim_client.connect(owner).set_appeal_quorum(2);
+    assert_eq!(old_owner_result, Err(ClaimError::InsufficientPermissions));
+
+    claim_client
+        .connect(new_owner)
+        .set_appeal_quorum(2)
+        .expect("set_appeal_quorum should succeed for new owner");
+}
+
+#[test]
+fn test_timing_changes_take_effect_after_delay() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+
+    let receiver_bytes = [46u8; 29];
+    let receiver = Principal::from_slice(&receiver_bytes);
+    let amount = Nat::from(1_000_000u64);
+    let desc = String::from("Delayed config change");
+    let new_timeout = 24 * 60 * 60 * 1_000_000_000u64; // 1 day in nanoseconds
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+        owner,
+        coverage_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    transfer_to_subaccount(
+        &mut ledger_client,
+        owner,
+        claim_canister,
+        subaccount,
+        Nat::from(1_000_000u64),
+    );
+    let claim_id = claim_client
+        .connect(owner)
+        .add_claim(coverage_id, incident_id, amount, pool_canister, desc)
+        .expect("add_claim should succeed");
+    claim_client
+        .connect(approver())
+        .approve_claim(claim_id, None, "Loss verified".to_string())
+        .expect("approve_claim should succeed");
+
+    // Parameters are validated
+    let invalid_result = claim_client.connect(owner).set_execution_timeout(0);
+    assert_eq!(invalid_result, Err(ClaimError::InvalidConfig));
+    let invalid_ledger = claim_client
+        .connect(owner)
+        .set_ledger_canister_id(Principal::anonymous());
+    assert_eq!(invalid_ledger, Err(ClaimError::InvalidConfig));
+
+    let non_owner_result = claim_client
+        .connect(receiver)
+        .set_execution_timeout(new_timeout);
+    assert_eq!(non_owner_result, Err(ClaimError::InsufficientPermissions));
+
+    claim_client
+        .connect(owner)
+        .set_execution_timeout(new_timeout)
+        .expect("set_execution_timeout should succeed for owner");
+
+    // The change is scheduled rather than applied immediately
+    let config = claim_client.connect(owner).get_config();
+    assert_eq!(config.execution_timeout, EXECUTION_TIMEOUT_NANOS);
+    assert_eq!(config.pending_changes.len(), 1);
+    assert_eq!(
+        config.pending_changes[0].change,
+        ConfigChange::ExecutionTimeout(new_timeout)
+    );
+
+    // Once the delay has passed the shorter timeout applies to the approved claim
+    pic.advance_time(Duration::from_nanos(CONFIG_CHANGE_DELAY));
+
+    // Queries already report the due change before any update applies it
+    let config = claim_client.connect(owner).get_config();
+    assert_eq!(config.execution_timeout, new_timeout);
+    assert!(config.pending_changes.is_empty());
+
+    claim_client
+        .connect(receiver)
+        .execute_claim(claim_id)
+        .expect("execute_claim should succeed under the new timeout");
+
+    // Timing overrides of a registered pool are delayed as well
+    let invalid_override =
+        claim_client
+            .connect(owner)
+            .register_pool(pool_canister, None, Some(0), None, vec![]);
+    assert_eq!(invalid_override, Err(ClaimError::InvalidConfig));
+
+    claim_client
+        .connect(owner)
+        .register_pool(pool_canister, None, Some(new_timeout * 2), None, vec![])
+        .expect("register_pool should succeed for owner");
+
+    let pool = claim_client
+        .connect(owner)
+        .get_registered_pool(pool_canister)
+        .unwrap();
+    assert_eq!(pool.execution_timeout, None);
+    let config = claim_client.connect(owner).get_config();
+    assert_eq!(
+        config.pending_changes[0].change,
+        ConfigChange::PoolExecutionTimeout {
+            pool_canister_id: pool_canister,
+            execution_timeout: Some(new_timeout * 2),
+        }
+    );
+
+    pic.advance_time(Duration::from_nanos(CONFIG_CHANGE_DELAY));
+
+    let pool = claim_client
+        .connect(owner)
+        .get_registered_pool(pool_canister)
+        .unwrap();
+    assert_eq!(pool.execution_timeout, Some(new_timeout * 2));
+}
+
+#[test]
+fn test_claims_are_filed_for_coverage_by_holder_or_delegate() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+
+    let delegate_bytes = [47u8; 29];
+    let delegate = Principal::from_slice(&delegate_bytes);
+    let payout_bytes = [48u8; 29];
+    let payout_account = Principal::from_slice(&payout_bytes);
+    let amount = Nat::from(1_000_000u64);
+    let desc = String::from("Delegated claim");
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    // Fund the delegate
+    let transfer_args = pool_canister::types::TransferArg {
+        from_subaccount: None,
+        to: pool_canister::types::Account {
+            owner: delegate,
+            subaccount: None,
+        },
+        amount: Nat::from(10_000_000u64),
+        fee: Some(TRANSFER_FEE.clone()),
+        memo: None,
+        created_at_time: None,
+    };
+    ledger_client.connect(owner).icrc1_transfer(transfer_args);
+
+    let subaccount = claim_client.connect(delegate).get_claim_deposit_subaccount(
+        delegate,
+        coverage_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    transfer_to_subaccount(
+        &mut ledger_client,
+        delegate,
+        claim_canister,
+        subaccount,
+        Nat::from(1_000_000u64),
+    );
+
+    // Only the covered account and its delegates may file claims
+    let unauthorized_result = claim_client.connect(delegate).add_claim(
+        coverage_id,
+        incident_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    assert_eq!(
+        unauthorized_result,
+        Err(ClaimError::NotAuthorizedForCoverage)
+    );
+
+    claim_client
+        .connect(owner)
+        .add_delegate(delegate)
+        .expect("add_delegate should succeed");
+    claim_client
+        .connect(owner)
+        .set_payout_account(Some(payout_account))
+        .expect("set_payout_account should succeed");
+    assert_eq!(
+        claim_client.connect(owner).get_delegates(owner),
+        vec![delegate]
+    );
+
+    // Claims cannot exceed the coverage or refer to unknown coverages
+    let excessive_result = claim_client.connect(delegate).add_claim(
+        coverage_id,
+        incident_id,
+        Nat::from(100_000_000_000u64),
+        pool_canister,
+        desc.clone(),
+    );
+    assert_eq!(excessive_result, Err(ClaimError::ClaimExceedsCoverage));
+
+    let unknown_result = claim_client.connect(delegate).add_claim(
+        coverage_id + 1,
+        incident_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    assert_eq!(unknown_result, Err(ClaimError::CoverageNotFound));
+
+    // The payout goes to the account designated by the covered account
+    let claim_id = claim_client
+        .connect(delegate)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            amount,
+            pool_canister,
+            desc.clone(),
+        )
+        .expect("add_claim should succeed for delegate");
+
+    let claim_info = claim_client.connect(owner).get_claim(claim_id).unwrap();
+    assert_eq!(claim_info.proposer, delegate);
+    assert_eq!(claim_info.covered_account, owner);
+    assert_eq!(claim_info.receiver, payout_account);
+
+    // Removed delegates can no longer file claims
+    claim_client
+        .connect(owner)
+        .remove_delegate(delegate)
+        .expect("remove_delegate should succeed");
+    let removed_result = claim_client.connect(delegate).add_claim(
+        coverage_id,
+        incident_id,
+        Nat::from(2_000_000u64),
+        pool_canister,
+        desc,
+    );
+    assert_eq!(removed_result, Err(ClaimError::NotAuthorizedForCoverage));
+}
+
+#[test]
+fn test_claims_on_one_coverage_cannot_exceed_it_together() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+
+    let first_amount = Nat::from(40_000_000_000u64);
+    let second_amount = Nat::from(30_000_000_000u64);
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+        owner,
+        coverage_id,
+        first_amount.clone(),
+        pool_canister,
+        "First loss".to_string(),
+    );
+    transfer_to_subaccount(
+        &mut ledger_client,
+        owner,
+        claim_canister,
+        subaccount,
+        Nat::from(1_000_000u64),
+    );
+    let first_claim = claim_client
+        .connect(owner)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            first_amount.clone(),
+            pool_canister,
+            "First loss".to_string(),
+        )
+        .expect("add_claim should succeed");
+
+    // Each claim fits the cover, but not both together
+    let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+        owner,
+        coverage_id,
+        second_amount.clone(),
+        pool_canister,
+        "Second loss".to_string(),
+    );
+    transfer_to_subaccount(
+        &mut ledger_client,
+        owner,
+        claim_canister,
+        subaccount,
+        Nat::from(1_000_000u64),
+    );
+    let second_result = claim_client.connect(owner).add_claim(
+        coverage_id,
+        incident_id,
+        second_amount.clone(),
+        pool_canister,
+        "Second loss".to_string(),
+    );
+    assert_eq!(second_result, Err(ClaimError::ClaimExceedsCoverage));
+
+    // A partial approval only holds the approved amount against the cover
+    claim_client
+        .connect(approver())
+        .approve_claim(
+            first_claim,
+            Some(Nat::from(20_000_000_000u64)),
+            "Part of the loss verified".to_string(),
+        )
+        .expect("approve_claim should succeed");
+
+    claim_client
+        .connect(owner)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            second_amount,
+            pool_canister,
+            "Second loss".to_string(),
+        )
+        .expect("add_claim should succeed within the remaining cover");
+
+    // Appealing for the full amount would no longer fit next to the second claim
+    let appeal_result = claim_client
+        .connect(owner)
+        .open_appeal(first_claim, "The whole loss is covered".to_string());
+    assert_eq!(appeal_result, Err(ClaimError::ClaimExceedsCoverage));
+}
+
+fn file_owner_claim(
+    claim_client: &mut ClaimCanisterClient,
+    ledger_client: &mut LedgerCanisterClient,
+    owner: Principal,
+    coverage_id: u64,
+    incident_id: u64,
+    pool_canister: Principal,
+    desc: &str,
+) -> u64 {
+    let amount = Nat::from(1_000_000u64);
+    let claim_canister = claim_client.client.canister_id;
+    let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+        owner,
+        coverage_id,
+        amount.clone(),
+        pool_canister,
+        desc.to_string(),
+    );
+    transfer_to_subaccount(
+        ledger_client,
+        owner,
+        claim_canister,
+        subaccount,
+        amount.clone(),
+    );
+    claim_client
+        .connect(owner)
+        .add_claim(
+            coverage_id,
+            incident_id,
+            amount,
+            pool_canister,
+            desc.to_string(),
+        )
+        .expect("add_claim should succeed")
+}
+
+fn report_incident_via_oracle(
+    pic: &pocket_ic::PocketIc,
+    oracle: Principal,
+    claim_canister: Principal,
+    pool_canister: Principal,
+    product_id: u64,
+    trigger_id: &str,
+    incident_time: u64,
+) -> Result<u64, String> {
+    let response = pic
+        .update_call(
+            oracle,
+            Principal::anonymous(),
+            "report_incident",
+            encode_args((
+                claim_canister,
+                pool_canister,
+                product_id,
+                trigger_id.to_string(),
+                incident_time,
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+    Decode!(&response, Result<u64, String>).unwrap()
+}
+
+#[test]
+fn test_parametric_incident_auto_approves_claims() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+    let oracle = install_mock_oracle(&pic, claim_canister, owner);
+
+    let stranger_bytes = [49u8; 29];
+    let stranger = Principal::from_slice(&stranger_bytes);
+    let amount = Nat::from(1_000_000u64);
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let product_id = pool_client.get_coverage(coverage_id).unwrap().product_id;
+    assert!(claim_client.is_oracle(oracle));
+
+    let reported_incident_id = report_pool_incident(&pic, pool_canister, owner, coverage_id);
+    let early_claim_id = file_owner_claim(
+        &mut claim_client,
+        &mut ledger_client,
+        owner,
+        coverage_id,
+        reported_incident_id,
+        pool_canister,
+        "Depeg loss before the report",
+    );
+
+    let incident_time = pic.get_time().as_nanos_since_unix_epoch();
+
+    // Only registered oracles may report, and only for products with a matching trigger
+    let stranger_result = claim_client.connect(stranger).report_incident(
+        pool_canister,
+        product_id,
+        "usdc-depeg".to_string(),
+        incident_time,
+    );
+    assert_eq!(stranger_result, Err(ClaimError::NotOracle));
+
+    let untriggered_result = report_incident_via_oracle(
+        &pic,
+        oracle,
+        claim_canister,
+        pool_canister,
+        product_id,
+        "usdc-depeg",
+        incident_time,
+    );
+    assert!(untriggered_result.is_err());
+
+    pool_client
+        .connect(owner)
+        .set_product_parametric_trigger(
+            product_id,
+            Some(pool_canister::ParametricTrigger {
+                trigger_id: "usdc-depeg".to_string(),
+                description: "USDC trades below 0.95".to_string(),
+            }),
+        )
+        .expect("set_product_parametric_trigger should succeed");
+
+    let mismatched_result = report_incident_via_oracle(
+        &pic,
+        oracle,
+        claim_canister,
+        pool_canister,
+        product_id,
+        "bridge-halt",
+        incident_time,
+    );
+    assert!(mismatched_result.is_err());
+
+    let future_result = report_incident_via_oracle(
+        &pic,
+        oracle,
+        claim_canister,
+        pool_canister,
+        product_id,
+        "usdc-depeg",
+        incident_time + APPROVAL_PERIOD_NANOS,
+    );
+    assert!(future_result.is_err());
+    assert!(claim_client.get_incidents().is_empty());
+
+    // A valid report approves pending claims for coverages active at the incident time
+    let incident_id = report_incident_via_oracle(
+        &pic,
+        oracle,
+        claim_canister,
+        pool_canister,
+        product_id,
+        "usdc-depeg",
+        incident_time,
+    )
+    .expect("report_incident should succeed");
+
+    let incident = claim_client.get_incident(incident_id).unwrap();
+    assert_eq!(incident.oracle, oracle);
+    assert_eq!(incident.product_id, product_id);
+    assert_eq!(incident.incident_time, incident_time);
+
+    // The report is recorded in the pool's registry, which freezes purchases of the product
+    let pool_incident_id = incident.pool_incident_id.unwrap();
+    let pool_incident = pool_client.get_incident(pool_incident_id).unwrap();
+    assert_eq!(pool_incident.product_id, product_id);
+    assert_eq!(pool_incident.incident_time, incident_time / 1_000_000_000);
+    assert_eq!(pool_incident.reported_by, claim_canister);
+
+    // Claims filed for another incident of the product are left to the approvers
+    let early_claim = claim_client.get_claim(early_claim_id).unwrap();
+    assert_eq!(early_claim.status, ClaimStatus::Pending);
+    assert_eq!(early_claim.incident_id, None);
+
+    // Claims filed for the reported incident are approved on creation
+    let late_claim_id = file_owner_claim(
+        &mut claim_client,
+        &mut ledger_client,
+        owner,
+        coverage_id,
+        pool_incident_id,
+        pool_canister,
+        "Depeg loss after the report",
+    );
+    let late_claim = claim_client.get_claim(late_claim_id).unwrap();
+    assert_eq!(late_claim.status, ClaimStatus::Approved);
+    assert_eq!(late_claim.approved_by, Some(oracle));
+    assert_eq!(late_claim.approved_amount, Some(amount.clone()));
+    assert_eq!(late_claim.incident_id, Some(incident_id));
+
+    let decisions = claim_client.get_claim_decisions(late_claim_id);
+    assert_eq!(decisions.last().unwrap().actor, oracle);
+    assert_eq!(decisions.last().unwrap().kind, DecisionKind::Approve);
+
+    // Parametric approvals only wait for the short execution timeout
+    let early_result = claim_client.connect(owner).execute_claim(late_claim_id);
+    assert_eq!(early_result, Err(ClaimError::ExecutionTimeoutNotExpired));
+
+    pic.advance_time(Duration::from_nanos(PARAMETRIC_EXECUTION_TIMEOUT));
+    claim_client
+        .connect(owner)
+        .execute_claim(late_claim_id)
+        .expect("execute_claim should succeed after the parametric timeout");
+    assert_eq!(
+        claim_client.get_claim(late_claim_id).unwrap().status,
+        ClaimStatus::Executed
+    );
+}
+
+#[test]
+fn test_claim_incident_must_fall_within_coverage() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+
+    let amount = Nat::from(1_000_000u64);
+    let desc = String::from("Exploit loss");
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let coverage = pool_client.get_coverage(coverage_id).unwrap();
+    let coverage_start = coverage.start_time * 1_000_000_000;
+
+    let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+        owner,
+        coverage_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    transfer_to_subaccount(
+        &mut ledger_client,
+        owner,
+        claim_canister,
+        subaccount,
+        Nat::from(1_000_000u64),
+    );
+
+    // Claims refer to incidents in the pool's registry for the covered product
+    let unknown_result = claim_client.connect(owner).add_claim(
+        coverage_id,
+        1_000,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    assert_eq!(unknown_result, Err(ClaimError::IncidentNotFound));
+
+    let other_product_id = pool_client
+        .connect(owner)
+        .create_product(
+            "Unrelated product".to_string(),
+            500,
+            EPISODE_DURATION * 3,
+            10_000,
+        )
+        .unwrap();
+    let other_incident_id = pool_client
+        .connect(owner)
+        .report_incident(
+            other_product_id,
+            coverage.start_time,
+            IncidentSeverity::Medium,
+        )
+        .unwrap();
+    let other_product_result = claim_client.connect(owner).add_claim(
+        coverage_id,
+        other_incident_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    assert_eq!(other_product_result, Err(ClaimError::IncidentNotForProduct));
+
+    // Incidents before the cover was bought are not covered
+    let early_incident_id = report_pool_incident_at(
+        &pic,
+        pool_canister,
+        owner,
+        coverage_id,
+        coverage.start_time - 1,
+    );
+    let before_start_result = claim_client.connect(owner).add_claim(
+        coverage_id,
+        early_incident_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    assert_eq!(
+        before_start_result,
+        Err(ClaimError::IncidentOutsideCoverage)
+    );
+
+    let incident_id =
+        report_pool_incident_at(&pic, pool_canister, owner, coverage_id, coverage.start_time);
+    let claim_id = claim_client
+        .connect(owner)
+        .add_claim(coverage_id, incident_id, amount, pool_canister, desc)
+        .expect("add_claim should succeed for an incident within the coverage");
+
+    let claim_info = claim_client.get_claim(claim_id).unwrap();
+    assert_eq!(claim_info.incident_time, coverage_start);
+}
+
+#[test]
+fn test_approvers_can_register_incidents_for_claims() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, coverage_id) = setup();
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+    let pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let product_id = pool_client.get_coverage(coverage_id).unwrap().product_id;
+    let stranger = Principal::from_slice(&[8u8; 29]);
+    let incident_time = current_time_nanos(&pic);
+
+    // Only the owner and the pool's approvers may record incidents through the executor
+    let stranger_result = claim_client.connect(stranger).register_incident(
+        pool_canister,
+        product_id,
+        incident_time,
+        ClaimIncidentSeverity::Medium,
+    );
+    assert_eq!(stranger_result, Err(ClaimError::InsufficientPermissions));
+
+    let future_result = claim_client.connect(approver()).register_incident(
+        pool_canister,
+        product_id,
+        incident_time + 1_000_000_000,
+        ClaimIncidentSeverity::Medium,
+    );
+    assert_eq!(future_result, Err(ClaimError::InvalidIncidentTime));
+
+    // The pool manager never reports this incident, yet claims can be filed for it
+    let incident_id = claim_client
+        .connect(approver())
+        .register_incident(
+            pool_canister,
+            product_id,
+            incident_time,
+            ClaimIncidentSeverity::Medium,
+        )
+        .expect("register_incident should succeed for an approver");
+
+    let incident = pool_client.get_incident(incident_id).unwrap();
+    assert_eq!(incident.product_id, product_id);
+    assert_eq!(incident.reported_by, claim_canister);
+
+    let claim_id = file_owner_claim(
+        &mut claim_client,
+        &mut ledger_client,
+        owner,
+        coverage_id,
+        incident_id,
+        pool_canister,
+        "Loss from an incident the pool manager did not report",
+    );
+    assert_eq!(
+        claim_client.get_claim(claim_id).unwrap().status,
+        ClaimStatus::Pending
+    );
+}
+
+#[test]
+fn test_claims_reject_incidents_in_waiting_period() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, _coverage_id) = setup();
+
+    let amount = Nat::from(1_000_000u64);
+    let desc = String::from("Front-run exploit");
+    let waiting_period = 24 * 60 * 60;
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+
+    let product_id = pool_client
+        .connect(owner)
+        .create_product(
+            "Waiting period product".to_string(),
+            500,
+            EPISODE_DURATION * 3,
+            10_000,
+        )
+        .unwrap();
+    pool_client
+        .connect(owner)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+    pool_client
+        .connect(owner)
+        .set_product_waiting_period(product_id, waiting_period)
+        .unwrap();
+
+    let coverage_amount = Nat::from(1_000_000_000u64);
+    let premium = calculate_premium(EPISODE_DURATION, 500, coverage_amount.clone());
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        owner,
+        product_id,
+        owner,
+        EPISODE_DURATION,
+        coverage_amount,
+        premium,
+    )
+    .expect("purchase_coverage should succeed");
+
+    let coverage = pool_client
+        .get_coverages(owner)
+        .into_iter()
+        .find(|coverage| coverage.product_id == product_id)
+        .unwrap();
+
+    let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+        owner,
+        coverage.coverage_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    transfer_to_subaccount(
+        &mut ledger_client,
+        owner,
+        claim_canister,
+        subaccount,
+        Nat::from(1_000_000u64),
+    );
+
+    // Incidents between purchase and the start of protection are not covered
+    let waiting_incident_id =
+        report_pool_incident(&pic, pool_canister, owner, coverage.coverage_id);
+    let waiting_result = claim_client.connect(owner).add_claim(
+        coverage.coverage_id,
+        waiting_incident_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    assert_eq!(waiting_result, Err(ClaimError::IncidentInWaitingPeriod));
+
+    pic.advance_time(Duration::from_secs(waiting_period));
+
+    let incident_id = report_pool_incident_at(
+        &pic,
+        pool_canister,
+        owner,
+        coverage.coverage_id,
+        coverage.start_time,
+    );
+    claim_client
+        .connect(owner)
+        .add_claim(
+            coverage.coverage_id,
+            incident_id,
+            amount,
+            pool_canister,
+            desc,
+        )
+        .expect("add_claim should succeed once protection started");
+}
+
+#[test]
+fn test_claims_accepted_during_grace_period_after_expiry() {
+    let (pic, claim_canister, pool_canister, owner, ledger_id, _coverage_id) = setup();
+
+    let amount = Nat::from(1_000_000u64);
+    let desc = String::from("Exploit discovered after expiry");
+    let day = 24 * 60 * 60;
+    let claim_grace_period = 2 * day;
+
+    let mut claim_client = ClaimCanisterClient::new(&pic, claim_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+
+    let product_id = pool_client
+        .connect(owner)
+        .create_product(
+            "Grace period product".to_string(),
+            500,
+            EPISODE_DURATION * 3,
+            10_000,
+        )
+        .unwrap();
+    pool_client
+        .connect(owner)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+    pool_client
+        .connect(owner)
+        .set_product_claim_grace_period(product_id, claim_grace_period)
+        .unwrap();
+
+    let coverage_amount = Nat::from(1_000_000_000u64);
+    let premium = calculate_premium(EPISODE_DURATION, 500, coverage_amount.clone());
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        owner,
+        product_id,
+        owner,
+        EPISODE_DURATION,
+        coverage_amount,
+        premium,
+    )
+    .expect("purchase_coverage should succeed");
+
+    let coverage = pool_client
+        .get_coverages(owner)
+        .into_iter()
+        .find(|coverage| coverage.product_id == product_id)
+        .unwrap();
+    assert_eq!(
+        coverage.claim_deadline,
+        coverage.end_time + claim_grace_period
+    );
+
+    let subaccount = claim_client.connect(owner).get_claim_deposit_subaccount(
+        owner,
+        coverage.coverage_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    transfer_to_subaccount(
+        &mut ledger_client,
+        owner,
+        claim_canister,
+        subaccount,
+        Nat::from(1_000_000u64),
+    );
+
+    // One day after expiry, incidents inside the coverage period can still be claimed
+    pic.advance_time(Duration::from_secs(EPISODE_DURATION + day));
+
+    let after_end_incident_id = report_pool_incident_at(
+        &pic,
+        pool_canister,
+        owner,
+        coverage.coverage_id,
+        coverage.end_time,
+    );
+    let after_end_result = claim_client.connect(owner).add_claim(
+        coverage.coverage_id,
+        after_end_incident_id,
+        amount.clone(),
+        pool_canister,
+        desc.clone(),
+    );
+    assert_eq!(after_end_result, Err(ClaimError::IncidentOutsideCoverage));
+
+    let incident_id = report_pool_incident_at(
+        &pic,
+        pool_canister,
+        owner,
+        coverage.coverage_id,
+        coverage.end_time - 1,
+    );
+    claim_client
+        .connect(owner)
+        .add_claim(
+            coverage.coverage_id,
+            incident_id,
+            amount.clone(),
+            pool_canister,
+            desc.clone(),
+        )
+        .expect("add_claim should succeed during the grace period");
+
+    // After the grace period the coverage can no longer be claimed
+    pic.advance_time(Duration::from_secs(claim_grace_period));
+
+    let late_result = claim_client.connect(owner).add_claim(
+        coverage.coverage_id,
+        incident_id,
+        amount,
+        pool_canister,
+        desc,
+    );
+    assert_eq!(late_result, Err(ClaimError::ClaimDeadlinePassed));
+}
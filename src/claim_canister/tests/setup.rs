@@ -1,9 +1,11 @@
 use candid::{encode_args, Decode, Nat, Principal};
-use commons::utils::get_stakable_episode;
+use claim_canister::types::DepositSchedule;
+use commons::utils::{calculate_premium, get_stakable_episode, purchase_coverage};
+use commons::{LedgerCanisterClient, PoolCanisterClient};
 use pocket_ic::PocketIc;
 use pool_canister::{
     types::{Account, TransferArg},
-    TRANSFER_FEE,
+    ProductState, EPISODE_DURATION, TRANSFER_FEE,
 };
 
 #[path = "types.rs"]
@@ -12,9 +14,15 @@ use ledger_types::{ArchiveOptions, FeatureFlags, InitArgs, LedgerArg};
 
 const CLAIM_WASM_PATH: &str = "../../target/wasm32-unknown-unknown/release/claim_canister.wasm";
 const POOL_WASM_PATH: &str = "../../target/wasm32-unknown-unknown/release/pool_canister.wasm";
+const MOCK_ORACLE_WASM_PATH: &str =
+    "../../target/wasm32-unknown-unknown/release/mock_oracle_canister.wasm";
 const ICRC1_LEDGER_WASM_PATH: &str = "../../ic-icrc1-ledger.wasm";
 
-pub fn setup() -> (PocketIc, Principal, Principal, Principal, Principal) {
+pub fn approver() -> Principal {
+    Principal::from_slice(&[2u8; 29])
+}
+
+pub fn setup() -> (PocketIc, Principal, Principal, Principal, Principal, u64) {
     let pic = PocketIc::new();
 
     let owner_bytes = [1u8; 29];
@@ -93,6 +101,15 @@ pub fn setup() -> (PocketIc, Principal, Principal, Principal, Principal) {
         None,
     );
 
+    // Independent approver, so claims filed by the owner can be decided without a conflict of interest
+    pic.update_call(
+        claim_canister,
+        owner,
+        "add_approver",
+        encode_args((approver(),)).unwrap(),
+    )
+    .unwrap();
+
     let pool_wasm = std::fs::read(POOL_WASM_PATH).expect(
         "Build first: cargo build --target wasm32-unknown-unknown --release -p pool_canister",
     );
@@ -106,8 +123,24 @@ pub fn setup() -> (PocketIc, Principal, Principal, Principal, Principal) {
         None,
     );
 
-    // Fund pool with deposit
-    let current_episode: u64 = get_stakable_episode(&pic, pool_canister, owner);
+    // Claims can only be filed against registered pools
+    pic.update_call(
+        claim_canister,
+        owner,
+        "register_pool",
+        encode_args((
+            pool_canister,
+            None::<u64>,
+            None::<u64>,
+            None::<DepositSchedule>,
+            Vec::<Principal>::new(),
+        ))
+        .unwrap(),
+    )
+    .unwrap();
+
+    // Fund pool with deposit, staked long enough to back the coverage bought below
+    let current_episode: u64 = get_stakable_episode(&pic, pool_canister, owner) + 3;
 
     let sub_bytes = pic
         .query_call(
@@ -148,5 +181,71 @@ pub fn setup() -> (PocketIc, Principal, Principal, Principal, Principal) {
     )
     .unwrap();
 
-    (pic, claim_canister, pool_canister, owner, ledger_id)
+    // Cover the owner, so claims can be filed against a real coverage
+    let coverage_amount = Nat::from(60_000_000_000u64);
+    let annual_percent = 500u64;
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let product_id = pool_client
+        .connect(owner)
+        .create_product(
+            "Claim test product".to_string(),
+            annual_percent,
+            EPISODE_DURATION * 3,
+            10_000,
+        )
+        .expect("create_product should succeed");
+    pool_client
+        .connect(owner)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let premium = calculate_premium(EPISODE_DURATION, annual_percent, coverage_amount.clone());
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        owner,
+        product_id,
+        owner,
+        EPISODE_DURATION,
+        coverage_amount,
+        premium,
+    )
+    .expect("purchase_coverage should succeed");
+
+    let coverage_id = pool_client.get_coverages(owner)[0].coverage_id;
+
+    (
+        pic,
+        claim_canister,
+        pool_canister,
+        owner,
+        ledger_id,
+        coverage_id,
+    )
+}
+
+// Installs the mock oracle and registers it with the claim canister
+pub fn install_mock_oracle(
+    pic: &PocketIc,
+    claim_canister: Principal,
+    owner: Principal,
+) -> Principal {
+    let oracle_wasm = std::fs::read(MOCK_ORACLE_WASM_PATH).expect(
+        "Build first: cargo build --target wasm32-unknown-unknown --release -p mock_oracle_canister",
+    );
+    let oracle_canister = pic.create_canister();
+    pic.add_cycles(oracle_canister, 2_000_000_000_000);
+    pic.install_canister(oracle_canister, oracle_wasm, encode_args(()).unwrap(), None);
+
+    pic.update_call(
+        claim_canister,
+        owner,
+        "add_oracle",
+        encode_args((oracle_canister,)).unwrap(),
+    )
+    .unwrap();
+
+    oracle_canister
 }
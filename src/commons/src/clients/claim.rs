@@ -1,6 +1,10 @@
 use crate::CanisterClient;
 use candid::{Nat, Principal};
-use claim_canister::types::{ClaimError, ClaimInfo};
+use claim_canister::types::{
+    Appeal, ClaimComment, ClaimConfig, ClaimError, ClaimInfo, ClaimStatus, ConflictRules,
+    DecisionRecord, DepositSchedule, EvidenceItem, IncidentReport, IncidentSeverity,
+    IneligibilityReason, RegisteredPool,
+};
 
 pub struct ClaimCanisterClient<'a> {
     pub client: CanisterClient<'a>,
@@ -19,18 +23,69 @@ impl<'a> ClaimCanisterClient<'a> {
     }
 
     crate::canister_methods! {
-        update add_claim(receiver: Principal, amount: Nat, pool_canister: Principal, desc: String) -> Result<u64, ClaimError>;
-        update approve_claim(claim_id: u64) -> Result<(), ClaimError>;
+        update add_claim(coverage_id: u64, incident_id: u64, amount: Nat, pool_canister: Principal, desc: String) -> Result<u64, ClaimError>;
+        update approve_claim(claim_id: u64, approved_amount: Option<Nat>, rationale: String) -> Result<(), ClaimError>;
+        update accept_partial_approval(claim_id: u64) -> Result<(), ClaimError>;
+        update dispute_partial_approval(claim_id: u64, rationale: String) -> Result<(), ClaimError>;
+        update reject_claim(claim_id: u64, rationale: String) -> Result<(), ClaimError>;
         update execute_claim(claim_id: u64) -> Result<(), ClaimError>;
         update add_approver(approver: Principal) -> Result<(), ClaimError>;
         update remove_approver(approver: Principal) -> Result<(), ClaimError>;
         update withdraw_deposit(claim_id: u64) -> Result<(), ClaimError>;
-        update mark_as_spam(claim_id: u64) -> Result<(), ClaimError>;
-        update set_claim_deposit(new_deposit: Nat) -> Result<(), ClaimError>;
+        update mark_as_spam(claim_id: u64, rationale: String) -> Result<(), ClaimError>;
+        update propose_owner(new_owner: Principal) -> Result<(), ClaimError>;
+        update accept_ownership() -> Result<(), ClaimError>;
+        update set_approval_period(new_period: u64) -> Result<(), ClaimError>;
+        update set_execution_timeout(new_timeout: u64) -> Result<(), ClaimError>;
+        update set_ledger_canister_id(new_ledger_id: Principal) -> Result<(), ClaimError>;
+        update set_deposit_schedule(schedule: DepositSchedule) -> Result<(), ClaimError>;
+        update reconcile_claim(claim_id: u64) -> Result<ClaimStatus, ClaimError>;
+        update set_conflict_rules(rules: ConflictRules) -> Result<(), ClaimError>;
+        update add_claim_evidence(claim_id: u64, content_hash: Vec<u8>, mime_type: String, uri: String) -> Result<(), ClaimError>;
+        update add_claim_comment(claim_id: u64, text: String) -> Result<(), ClaimError>;
+        update add_delegate(delegate: Principal) -> Result<(), ClaimError>;
+        update remove_delegate(delegate: Principal) -> Result<(), ClaimError>;
+        update set_payout_account(payout_account: Option<Principal>) -> Result<(), ClaimError>;
+        update register_pool(pool_canister_id: Principal, approval_period: Option<u64>, execution_timeout: Option<u64>, deposit_schedule: Option<DepositSchedule>, approvers: Vec<Principal>) -> Result<(), ClaimError>;
+        update unregister_pool(pool_canister_id: Principal) -> Result<(), ClaimError>;
+        update open_appeal(claim_id: u64, rationale: String) -> Result<(), ClaimError>;
+        update vote_on_appeal(claim_id: u64, uphold: bool, rationale: String) -> Result<(), ClaimError>;
+        update withdraw_appeal_deposit(claim_id: u64) -> Result<(), ClaimError>;
+        update expire_appeal(claim_id: u64) -> Result<(), ClaimError>;
+        update collect_forfeited_appeal_deposit(claim_id: u64) -> Result<(), ClaimError>;
+        update add_appeal_committee_member(member: Principal) -> Result<(), ClaimError>;
+        update remove_appeal_committee_member(member: Principal) -> Result<(), ClaimError>;
+        update add_oracle(oracle: Principal) -> Result<(), ClaimError>;
+        update remove_oracle(oracle: Principal) -> Result<(), ClaimError>;
+        update report_incident(pool_canister_id: Principal, product_id: u64, trigger_id: String, incident_time: u64) -> Result<u64, ClaimError>;
+        update register_incident(pool_canister_id: Principal, product_id: u64, incident_time: u64, severity: IncidentSeverity) -> Result<u64, ClaimError>;
+        update set_appeal_deposit(new_deposit: Nat) -> Result<(), ClaimError>;
+        update set_appeal_quorum(new_quorum: u64) -> Result<(), ClaimError>;
 
         query get_claim(claim_id: u64) -> Option<ClaimInfo>;
+        query get_delegates(covered_account: Principal) -> Vec<Principal>;
+        query get_payout_account(covered_account: Principal) -> Principal;
         query is_approver(principal: Principal) -> bool;
-        query get_claim_deposit() -> Nat;
-        query get_claim_deposit_subaccount(user: Principal, receiver: Principal, amount: Nat, pool_canister_id: Principal, description: String) -> [u8; 32];
+        query get_owner() -> Principal;
+        query get_config() -> ClaimConfig;
+        query get_claim_deposit(pool_canister_id: Principal, amount: Nat) -> Nat;
+        query get_deposit_schedule() -> DepositSchedule;
+        query get_claim_evidence(claim_id: u64) -> Vec<EvidenceItem>;
+        query get_claim_comments(claim_id: u64) -> Vec<ClaimComment>;
+        query get_claim_decisions(claim_id: u64) -> Vec<DecisionRecord>;
+        query get_approver_decisions(approver: Principal) -> Vec<DecisionRecord>;
+        query get_approver_ineligibility(claim_id: u64, approver: Principal) -> Result<Vec<IneligibilityReason>, ClaimError>;
+        query get_conflict_rules() -> ConflictRules;
+        query get_registered_pool(pool_canister_id: Principal) -> Option<RegisteredPool>;
+        query get_registered_pools() -> Vec<RegisteredPool>;
+        query get_appeal(claim_id: u64) -> Option<Appeal>;
+        query get_appeal_deposit() -> Nat;
+        query get_appeal_quorum() -> u64;
+        query get_appeal_deposit_subaccount(claim_id: u64) -> [u8; 32];
+        query is_appeal_committee_member(principal: Principal) -> bool;
+        query is_oracle(principal: Principal) -> bool;
+        query get_incident(incident_id: u64) -> Option<IncidentReport>;
+        query get_incidents() -> Vec<IncidentReport>;
+        query get_claim_deposit_subaccount(user: Principal, coverage_id: u64, amount: Nat, pool_canister_id: Principal, description: String) -> [u8; 32];
     }
 }
@@ -1,7 +1,12 @@
 use crate::CanisterClient;
 use candid::{Nat, Principal};
 
-use pool_canister::{Coverage, Deposit, Episode, PoolError, PoolState, Product, UserDepositInfo};
+use pool_canister::{
+    Coverage, CoverageQuote, Deposit, DurationDiscount, Episode, FeeConfig, Incident,
+    IncidentSeverity, ParametricTrigger, PoolAnalytics, PoolError, PoolState, PricingCurve,
+    Product, ProductMetadata, ProductState, Referrer, SlashRecord, Subscription, UserDepositInfo,
+    VolumeDiscount,
+};
 
 
 pub struct PoolCanisterClient<'a> {
@@ -23,15 +28,38 @@ impl<'a> PoolCanisterClient<'a> {
     crate::canister_methods! {
         update deposit(user: Principal, episode: u64) -> Result<(), PoolError>;
         update withdraw(deposit_id: u64) -> Result<(), PoolError>;
-        update slash(receiver: Principal, amount: Nat) -> Result<(), PoolError>;
+        update slash(claim_id: u64, receiver: Principal, amount: Nat) -> Result<(), PoolError>;
+        update resolve_slash(claim_id: u64, transferred: bool) -> Result<(), PoolError>;
         update reward_pool() -> Result<(), PoolError>;
         update set_executor_principal(executor: Principal) -> Result<(), PoolError>;
         update set_pool_manager_principal(pool_manager: Principal) -> Result<(), PoolError>;
         update update_episodes_state() -> ();
         update withdraw_rewards(deposit_ids: Vec<u64>) -> Result<Nat, PoolError>;
         update create_product(name: String, annual_percent: u64, max_coverage_duration: u64, max_pool_allocation_percent: u64) -> Result<u64, PoolError>;
-        update set_product(product_id: u64, annual_percent: u64, max_coverage_duration: u64, max_pool_allocation_percent: u64, active: bool) -> Result<(), PoolError>;
-        update purchase_coverage(product_id: u64, covered_account: Principal, coverage_duration: u64, coverage_amount: Nat) -> Result<(), PoolError>;
+        update set_product(product_id: u64, annual_percent: u64, max_coverage_duration: u64, max_pool_allocation_percent: u64, pricing_curve: Option<PricingCurve>) -> Result<(), PoolError>;
+        update set_product_waiting_period(product_id: u64, waiting_period: u64) -> Result<(), PoolError>;
+        update set_product_claim_grace_period(product_id: u64, claim_grace_period: u64) -> Result<(), PoolError>;
+        update set_product_discounts(product_id: u64, duration_discounts: Vec<DurationDiscount>, volume_discounts: Vec<VolumeDiscount>) -> Result<(), PoolError>;
+        update set_product_minimums(product_id: u64, min_premium: Nat, min_coverage_amount: Nat) -> Result<(), PoolError>;
+        update set_product_coverage_limits(product_id: u64, max_coverage_per_account: Option<Nat>, max_coverage_per_purchase: Option<Nat>) -> Result<(), PoolError>;
+        update set_product_state(product_id: u64, state: ProductState) -> Result<(), PoolError>;
+        update set_product_metadata(product_id: u64, metadata: ProductMetadata) -> Result<(), PoolError>;
+        update set_product_parametric_trigger(product_id: u64, parametric_trigger: Option<ParametricTrigger>) -> Result<(), PoolError>;
+        update extend_coverage(coverage_id: u64, additional_duration: u64) -> Result<(), PoolError>;
+        update cancel_coverage(coverage_id: u64) -> Result<Nat, PoolError>;
+        update set_oracle_principal(oracle: Principal) -> Result<(), PoolError>;
+        update report_incident(product_id: u64, incident_time: u64, severity: IncidentSeverity) -> Result<u64, PoolError>;
+        update resolve_incident(incident_id: u64) -> Result<(), PoolError>;
+        update purchase_coverage(product_id: u64, covered_account: Principal, coverage_duration: u64, coverage_amount: Nat, referrer: Option<Principal>) -> Result<(), PoolError>;
+        update register_referrer(referrer: Principal, commission_bps: u64) -> Result<(), PoolError>;
+        update withdraw_referral_commissions() -> Result<Nat, PoolError>;
+        update subscribe_coverage(coverage_id: u64) -> Result<(), PoolError>;
+        update cancel_subscription(coverage_id: u64) -> Result<(), PoolError>;
+        update set_protocol_owner(new_owner: Principal) -> Result<(), PoolError>;
+        update set_protocol_fee(protocol_treasury: Principal, protocol_fee_bps: u64) -> Result<(), PoolError>;
+        update set_manager_fee(manager_fee_bps: u64) -> Result<(), PoolError>;
+        update claim_protocol_fees() -> Result<Nat, PoolError>;
+        update claim_manager_fees() -> Result<Nat, PoolError>;
 
         query get_deposit(deposit_id: u64) -> Option<Deposit>;
         query get_user_deposits(user: Principal) -> Vec<UserDepositInfo>;
@@ -41,11 +69,22 @@ impl<'a> PoolCanisterClient<'a> {
         query get_episode(episode_id: u64) -> Option<Episode>;
         query get_pool_state() -> PoolState;
         query get_pool_reward_rate() -> Nat;
+        query get_fee_config() -> FeeConfig;
+        query get_protocol_owner() -> Principal;
+        query get_referrer(referrer: Principal) -> Option<Referrer>;
+        query get_pool_analytics() -> PoolAnalytics;
         query get_reward_subaccount() -> [u8; 32];
         query get_deposits_rewards(deposit_ids: Vec<u64>) -> Nat;
-        query get_products() -> Vec<Product>;
+        query get_product(product_id: u64) -> Option<Product>;
+        query quote_coverage(product_id: u64, coverage_duration: u64, coverage_amount: Nat, covered_account: Option<Principal>) -> Result<CoverageQuote, PoolError>;
+        query get_products(state: Option<ProductState>) -> Vec<Product>;
         query get_total_cover_allocation() -> Nat;
         query get_coverages(user: Principal) -> Vec<Coverage>;
         query get_coverage(coverage_id: u64) -> Option<Coverage>;
+        query get_subscription(coverage_id: u64) -> Option<Subscription>;
+        query get_oracle_principal() -> Principal;
+        query get_incident(incident_id: u64) -> Option<Incident>;
+        query get_product_incidents(product_id: u64) -> Vec<Incident>;
+        query get_slash_record(claim_id: u64) -> Option<SlashRecord>;
     }
 }
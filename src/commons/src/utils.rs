@@ -176,6 +176,7 @@ pub fn purchase_coverage(
         covered_account,
         coverage_duration,
         coverage_amount,
+        None,
     );
     match result {
         Ok(()) => Ok(()),
@@ -202,8 +203,11 @@ pub fn calculate_premium(coverage_duration: u64, annual_percent: u64, coverage_a
     const BASIS_POINTS: u64 = 10_000;
     const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
 
-    (Nat::from(coverage_duration) * Nat::from(annual_percent) * coverage_amount)
-        / (Nat::from(SECONDS_PER_YEAR) * Nat::from(BASIS_POINTS))
+    let denominator = Nat::from(SECONDS_PER_YEAR) * Nat::from(BASIS_POINTS);
+    (Nat::from(coverage_duration) * Nat::from(annual_percent) * coverage_amount
+        + denominator.clone()
+        - Nat::from(1u64))
+        / denominator
 }
 
 /// Macro for assertions with allowed error
@@ -0,0 +1,28 @@
+use candid::{Principal, Reserved};
+use ic_cdk::api::call::call;
+
+// Test double for an incident oracle: forwards whatever it is told to report to the claim
+// canister, which authenticates the report by this canister's principal.
+#[ic_cdk::update]
+async fn report_incident(
+    claim_canister_id: Principal,
+    pool_canister_id: Principal,
+    product_id: u64,
+    trigger_id: String,
+    incident_time: u64,
+) -> Result<u64, String> {
+    let report_result: Result<(Result<u64, Reserved>,), _> = call(
+        claim_canister_id,
+        "report_incident",
+        (pool_canister_id, product_id, trigger_id, incident_time),
+    )
+    .await;
+
+    match report_result {
+        Ok((Ok(incident_id),)) => Ok(incident_id),
+        Ok((Err(_),)) => Err("Incident report rejected by the claim canister".to_string()),
+        Err(e) => Err(format!("{:?}", e)),
+    }
+}
+
+ic_cdk::export_candid!();
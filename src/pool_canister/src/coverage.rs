@@ -1,20 +1,39 @@
 use crate::episodes::{get_current_episode, process_episodes};
+use crate::fees::accrue_premium_fees;
+use crate::incidents::has_open_incident;
 use crate::ledger::{get_purchase_subaccount, get_subaccount_balance, transfer_icrc1};
+use crate::pricing::{calculate_premium, product_capacity, verify_premium, BASIS_POINTS};
+use crate::referrals::{accrue_referral_commission, verify_referrer};
 use crate::rewards::reward_pool_with_duration;
 use crate::storage::*;
-use crate::types::{Coverage, Episode, PoolError, Product, StorableNat, UserCoverages};
-use crate::{EPISODE_DURATION, MAX_ACTIVE_EPISODES, TRANSFER_FEE};
+use crate::subscriptions::end_subscription;
+use crate::types::{
+    Coverage, CoverageStatus, DurationDiscount, Episode, ParametricTrigger, PoolError,
+    PricingCurve, Product, ProductMetadata, ProductState, RiskRating, StorableNat, UserCoverages,
+    VolumeDiscount,
+};
+use crate::{EPISODE_DURATION, MAX_ACTIVE_EPISODES, PRECISION_SCALE, TRANSFER_FEE};
 use candid::{Nat, Principal};
 
-const BASIS_POINTS: u64 = 10_000;
-const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+const CANCELLATION_FEE_BPS: u64 = 500;
+
+// The capital behind a cover stays locked until its claim deadline, which must fall within
+// the active episodes.
+fn fits_active_episodes(
+    waiting_period: u64,
+    max_coverage_duration: u64,
+    claim_grace_period: u64,
+) -> bool {
+    waiting_period + max_coverage_duration + claim_grace_period
+        < (MAX_ACTIVE_EPISODES - 1) * EPISODE_DURATION
+}
 
 fn update_product_allocation(product: &mut Product) {
     product.allocation = compute_current_product_allocation(product);
     product.last_allocation_update = ic_cdk::api::time() / 1_000_000_000;
 }
 
-fn compute_current_product_allocation(product: &Product) -> Nat {
+pub fn compute_current_product_allocation(product: &Product) -> Nat {
     let last_updated_episode = product.last_allocation_update / EPISODE_DURATION;
     let current_episode = get_current_episode();
 
@@ -40,56 +59,122 @@ fn compute_current_product_allocation(product: &Product) -> Nat {
     })
 }
 
-fn verify_product_allocation(last_covered_episode: u64, requested_allocation: Nat) -> bool {
-    let current_episode = get_current_episode();
+pub fn verify_product_allocation(last_covered_episode: u64, requested_allocation: Nat) -> bool {
     let pool_state = POOL_STATE.with(|state| state.borrow().get().clone());
 
     if pool_state.total_shares == Nat::from(0u64) {
         return false;
     }
 
+    available_pool_allocation(last_covered_episode) >= requested_allocation
+}
+
+// The assets of the episodes that stay staked at least until `last_covered_episode`.
+pub fn available_pool_allocation(last_covered_episode: u64) -> Nat {
+    let current_episode = get_current_episode();
+    let pool_state = POOL_STATE.with(|state| state.borrow().get().clone());
+
+    if pool_state.total_shares == Nat::from(0u64) {
+        return Nat::from(0u64);
+    }
+
     EPISODES.with(|episodes| {
         let episodes_ref = episodes.borrow();
         let mut available_allocation = Nat::from(0u64);
 
         for i in last_covered_episode..(current_episode + MAX_ACTIVE_EPISODES) {
-            let episode_allocation = if let Some(episode) = episodes_ref.get(&i) {
+            if let Some(episode) = episodes_ref.get(&i) {
                 if episode.episode_shares > Nat::from(0u64) {
-                    episode.episode_shares.clone() * pool_state.total_assets.clone()
-                        / pool_state.total_shares.clone()
-                } else {
-                    Nat::from(0u64)
+                    available_allocation += episode.episode_shares.clone()
+                        * pool_state.total_assets.clone()
+                        / pool_state.total_shares.clone();
                 }
-            } else {
-                Nat::from(0u64)
-            };
-
-            available_allocation += episode_allocation;
-
-            if available_allocation >= requested_allocation {
-                return true;
             }
         }
 
-        false
+        available_allocation
     })
 }
 
+// Releases `amount` of a product's allocation, and of the pool's cover, at the end of `episode_id`.
+fn add_allocation_cut(product_id: u64, episode_id: u64, amount: &Nat) {
+    EPISODE_ALLOCATION_CUT.with(|cuts| {
+        let mut cuts_ref = cuts.borrow_mut();
+        let key = (product_id, episode_id);
+        let current_cut = cuts_ref.get(&key).unwrap_or(StorableNat(Nat::from(0u64)));
+        cuts_ref.insert(key, StorableNat(current_cut.0 + amount.clone()));
+    });
+
+    EPISODES.with(|episodes| {
+        let mut episodes_ref = episodes.borrow_mut();
+        let mut episode = episodes_ref.get(&episode_id).unwrap_or(Episode {
+            episode_shares: Nat::from(0u64),
+            assets_staked: Nat::from(0u64),
+            reward_decrease: Nat::from(0u64),
+            coverage_decrease: Nat::from(0u64),
+            acc_reward_per_share_on_expire: Nat::from(0u64),
+        });
+        episode.coverage_decrease += amount.clone();
+        episodes_ref.insert(episode_id, episode);
+    });
+}
+
+fn remove_allocation_cut(product_id: u64, episode_id: u64, amount: &Nat) {
+    EPISODE_ALLOCATION_CUT.with(|cuts| {
+        let mut cuts_ref = cuts.borrow_mut();
+        let key = (product_id, episode_id);
+        if let Some(current_cut) = cuts_ref.get(&key) {
+            cuts_ref.insert(key, StorableNat(current_cut.0 - amount.clone()));
+        }
+    });
+
+    EPISODES.with(|episodes| {
+        let mut episodes_ref = episodes.borrow_mut();
+        if let Some(mut episode) = episodes_ref.get(&episode_id) {
+            episode.coverage_decrease -= amount.clone();
+            episodes_ref.insert(episode_id, episode);
+        }
+    });
+}
+
+// Stops `rate` of a reward stream that was set to end with `reward_episode`.
+fn remove_reward_rate(reward_episode: u64, rate: &Nat) {
+    POOL_REWARD_RATE.with(|cell| {
+        let current_rate = cell.borrow().get().clone().0;
+        cell.borrow_mut()
+            .set(StorableNat(current_rate - rate.clone()))
+            .ok();
+    });
+
+    EPISODES.with(|episodes| {
+        let mut episodes_ref = episodes.borrow_mut();
+        if let Some(mut episode) = episodes_ref.get(&reward_episode) {
+            episode.reward_decrease -= rate.clone();
+            episodes_ref.insert(reward_episode, episode);
+        }
+    });
+}
+
 #[ic_cdk::update]
 pub async fn purchase_coverage(
     product_id: u64,
     covered_account: Principal,
     coverage_duration: u64,
     coverage_amount: Nat,
+    referrer: Option<Principal>,
 ) -> Result<(), PoolError> {
     let mut product = PRODUCTS
         .with(|products| products.borrow().get(&product_id))
         .ok_or(PoolError::ProductNotFound)?;
 
-    if !product.active {
+    if product.state != ProductState::Active {
         return Err(PoolError::ProductNotActive);
     }
 
+    if has_open_incident(product_id) {
+        return Err(PoolError::ProductFrozen);
+    }
+
     if coverage_duration > product.max_coverage_duration {
         return Err(PoolError::CoverageDurationTooLong);
     }
@@ -98,15 +183,37 @@ pub async fn purchase_coverage(
         return Err(PoolError::CoverageDurationTooShort);
     }
 
+    if coverage_amount < product.min_coverage_amount {
+        return Err(PoolError::CoverageAmountTooSmall);
+    }
+
+    if let Some(max_coverage_per_purchase) = &product.max_coverage_per_purchase {
+        if coverage_amount > *max_coverage_per_purchase {
+            return Err(PoolError::CoverageAmountTooLarge);
+        }
+    }
+
     if covered_account == Principal::anonymous() {
         return Err(PoolError::InvalidProductParameters);
     }
 
+    verify_referrer(referrer)?;
+
     process_episodes();
     update_product_allocation(&mut product);
 
+    // Protection only starts once the product's waiting period has passed.
     let current_time = ic_cdk::api::time() / 1_000_000_000;
-    let last_covered_episode = (current_time + coverage_duration) / EPISODE_DURATION;
+    let start_time = current_time + product.waiting_period;
+    let end_time = start_time + coverage_duration;
+    let claim_deadline = end_time + product.claim_grace_period;
+    let last_covered_episode = claim_deadline / EPISODE_DURATION;
+
+    if remaining_account_coverage(&product, covered_account, current_time)
+        .is_some_and(|remaining| coverage_amount > remaining)
+    {
+        return Err(PoolError::AccountCoverageLimitExceeded);
+    }
 
     let new_total_allocation = coverage_amount.clone() + product.allocation.clone();
     let required_pool_allocation = new_total_allocation.clone() * Nat::from(BASIS_POINTS)
@@ -116,9 +223,15 @@ pub async fn purchase_coverage(
         return Err(PoolError::NotEnoughAssetsToCover);
     }
 
-    let premium_amount = (Nat::from(coverage_duration) * Nat::from(product.annual_percent)
-        * coverage_amount.clone())
-        / (Nat::from(SECONDS_PER_YEAR) * Nat::from(BASIS_POINTS));
+    let capacity = product_capacity(&product, last_covered_episode);
+    let premium_amount = calculate_premium(
+        &product,
+        coverage_duration,
+        &coverage_amount,
+        &product.allocation,
+        &capacity,
+    );
+    verify_premium(&premium_amount)?;
 
     let caller = ic_cdk::caller();
     let purchase_subaccount = get_purchase_subaccount(caller, product_id);
@@ -134,14 +247,14 @@ pub async fn purchase_coverage(
         return Err(PoolError::InsufficientBalance);
     }
 
-    EPISODE_ALLOCATION_CUT.with(|cuts| {
-        let mut cuts_ref = cuts.borrow_mut();
-        let key = (product_id, last_covered_episode);
-        let current_cut = cuts_ref
-            .get(&key)
-            .unwrap_or(StorableNat(Nat::from(0u64)));
-        cuts_ref.insert(key, StorableNat(current_cut.0 + coverage_amount.clone()));
-    });
+    // Other purchases for the same account may have completed while the balance was being fetched.
+    if remaining_account_coverage(&product, covered_account, current_time)
+        .is_some_and(|remaining| coverage_amount > remaining)
+    {
+        return Err(PoolError::AccountCoverageLimitExceeded);
+    }
+
+    add_allocation_cut(product_id, last_covered_episode, &coverage_amount);
 
     product.allocation += coverage_amount.clone();
     PRODUCTS.with(|products| {
@@ -155,20 +268,6 @@ pub async fn purchase_coverage(
             .ok();
     });
 
-    EPISODES.with(|episodes| {
-        let mut episodes_ref = episodes.borrow_mut();
-        let mut episode = episodes_ref.get(&last_covered_episode).unwrap_or(Episode {
-            episode_shares: Nat::from(0u64),
-            assets_staked: Nat::from(0u64),
-            reward_decrease: Nat::from(0u64),
-            coverage_decrease: Nat::from(0u64),
-            acc_reward_per_share_on_expire: Nat::from(0u64),
-        });
-        episode.coverage_decrease += coverage_amount.clone();
-        episodes_ref.insert(last_covered_episode, episode);
-    });
-
-
     transfer_icrc1(
         Some(purchase_subaccount.to_vec()),
         ic_cdk::api::id(),
@@ -186,6 +285,11 @@ pub async fn purchase_coverage(
         .await?;
     }
 
+    let net_premium = premium_amount.clone() - TRANSFER_FEE.clone();
+    let commission = accrue_referral_commission(referrer, &premium_amount, &net_premium);
+    let reward_amount = accrue_premium_fees(&premium_amount, net_premium) - commission;
+    let reward_rate = reward_pool_with_duration(reward_amount, end_time - current_time);
+
     let coverage_id = COVERAGE_COUNTER.with(|counter| {
         let current = counter.borrow().get().clone();
         let new_counter = current + 1;
@@ -200,8 +304,13 @@ pub async fn purchase_coverage(
         product_id,
         coverage_amount: coverage_amount.clone(),
         premium_amount: premium_amount.clone(),
-        start_time: current_time,
-        end_time: current_time + coverage_duration,
+        purchased_at: current_time,
+        start_time,
+        end_time,
+        claim_deadline,
+        status: CoverageStatus::Active,
+        reward_rate,
+        referrer,
     };
 
     COVERAGES.with(|coverages| {
@@ -217,8 +326,357 @@ pub async fn purchase_coverage(
         user_coverages_ref.insert(caller, user_coverage_list);
     });
 
-    let reward_amount = premium_amount.clone() - TRANSFER_FEE.clone();
-    reward_pool_with_duration(reward_amount, coverage_duration);
+    COVERED_ACCOUNT_COVERAGES.with(|account_coverages| {
+        let mut account_coverages_ref = account_coverages.borrow_mut();
+        let mut account_coverage_list = account_coverages_ref
+            .get(&covered_account)
+            .unwrap_or(UserCoverages(vec![]));
+        account_coverage_list.0.push(coverage_id);
+        account_coverages_ref.insert(covered_account, account_coverage_list);
+    });
+
+    Ok(())
+}
+
+// Coverages bought before covered accounts were indexed are added to the index on upgrade.
+pub fn index_covered_account_coverages() {
+    let coverages: Vec<Coverage> = COVERAGES.with(|coverages| {
+        coverages
+            .borrow()
+            .iter()
+            .map(|(_, coverage)| coverage)
+            .collect()
+    });
+
+    COVERED_ACCOUNT_COVERAGES.with(|account_coverages| {
+        let mut account_coverages_ref = account_coverages.borrow_mut();
+        for coverage in coverages {
+            let mut account_coverage_list = account_coverages_ref
+                .get(&coverage.covered_account)
+                .unwrap_or(UserCoverages(vec![]));
+            if !account_coverage_list.0.contains(&coverage.coverage_id) {
+                account_coverage_list.0.push(coverage.coverage_id);
+                account_coverages_ref.insert(coverage.covered_account, account_coverage_list);
+            }
+        }
+    });
+}
+
+// The cover a covered account may still buy under a product that caps it per account.
+pub fn remaining_account_coverage(
+    product: &Product,
+    covered_account: Principal,
+    current_time: u64,
+) -> Option<Nat> {
+    product
+        .max_coverage_per_account
+        .as_ref()
+        .map(|max_coverage_per_account| {
+            let account_coverage =
+                account_coverage_amount(covered_account, product.product_id, current_time);
+            if *max_coverage_per_account > account_coverage {
+                max_coverage_per_account.clone() - account_coverage
+            } else {
+                Nat::from(0u64)
+            }
+        })
+}
+
+// Sums the cover still in force for a covered account under one product.
+pub fn account_coverage_amount(
+    covered_account: Principal,
+    product_id: u64,
+    current_time: u64,
+) -> Nat {
+    let coverage_ids = COVERED_ACCOUNT_COVERAGES.with(|account_coverages| {
+        account_coverages
+            .borrow()
+            .get(&covered_account)
+            .map(|list| list.0)
+            .unwrap_or_default()
+    });
+
+    COVERAGES.with(|coverages| {
+        let coverages = coverages.borrow();
+        coverage_ids
+            .iter()
+            .filter_map(|coverage_id| coverages.get(coverage_id))
+            .filter(|coverage| {
+                coverage.product_id == product_id
+                    && coverage.status == CoverageStatus::Active
+                    && current_time < coverage.end_time
+            })
+            .fold(Nat::from(0u64), |total, coverage| {
+                total + coverage.coverage_amount
+            })
+    })
+}
+
+// Refunds the part of the premium that has not been streamed to LPs yet, minus a cancellation
+// fee that keeps streaming until the original end of the reward period.
+#[ic_cdk::update]
+pub async fn cancel_coverage(coverage_id: u64) -> Result<Nat, PoolError> {
+    let caller = ic_cdk::caller();
+
+    let mut coverage = COVERAGES
+        .with(|coverages| coverages.borrow().get(&coverage_id))
+        .ok_or(PoolError::CoverageNotFound)?;
+
+    if coverage.buyer != caller {
+        return Err(PoolError::NotCoverageBuyer);
+    }
+
+    process_episodes();
+
+    let current_time = ic_cdk::api::time() / 1_000_000_000;
+
+    if coverage.status != CoverageStatus::Active || current_time >= coverage.end_time {
+        return Err(PoolError::CoverageNotActive);
+    }
+
+    // Cover cannot be given up for a refund once an incident it may pay out for is open.
+    if has_open_incident(coverage.product_id) {
+        return Err(PoolError::ProductFrozen);
+    }
+
+    // Coverages bought before reward rates were recorded cannot tell what to refund.
+    if coverage.reward_rate == 0u64 {
+        return Err(PoolError::CoverageNotRefundable);
+    }
+
+    let reward_episode = coverage.end_time / EPISODE_DURATION;
+    let reward_end_time = (reward_episode + 1) * EPISODE_DURATION;
+    let refunded_rate = coverage.reward_rate.clone()
+        * Nat::from(BASIS_POINTS - CANCELLATION_FEE_BPS)
+        / Nat::from(BASIS_POINTS);
+    let refund_amount =
+        refunded_rate.clone() * Nat::from(reward_end_time - current_time) / PRECISION_SCALE.clone();
+
+    remove_reward_rate(reward_episode, &refunded_rate);
+
+    let last_covered_episode = coverage.claim_deadline / EPISODE_DURATION;
+    let coverage_amount = coverage.coverage_amount.clone();
+
+    remove_allocation_cut(coverage.product_id, last_covered_episode, &coverage_amount);
+
+    if let Some(mut product) = PRODUCTS.with(|products| products.borrow().get(&coverage.product_id))
+    {
+        update_product_allocation(&mut product);
+        product.allocation -= coverage_amount.clone();
+        PRODUCTS.with(|products| {
+            products.borrow_mut().insert(product.product_id, product);
+        });
+    }
+
+    TOTAL_COVER_ALLOCATION.with(|cell| {
+        let current_allocation = cell.borrow().get().clone().0;
+        cell.borrow_mut()
+            .set(StorableNat(current_allocation - coverage_amount.clone()))
+            .ok();
+    });
+
+    // Protection ends now, and so does the window for filing claims against it.
+    coverage.status = CoverageStatus::Cancelled;
+    coverage.end_time = current_time.max(coverage.start_time);
+    coverage.claim_deadline = current_time;
+
+    COVERAGES.with(|coverages| {
+        coverages.borrow_mut().insert(coverage_id, coverage);
+    });
+
+    end_subscription(coverage_id);
+
+    if refund_amount > TRANSFER_FEE.clone() {
+        transfer_icrc1(None, caller, refund_amount.clone()).await?;
+    }
+
+    Ok(refund_amount)
+}
+
+// Checks that `coverage` can be extended by `additional_duration` and returns the premium
+// owed for it at the product's current rate.
+pub fn prepare_coverage_extension(
+    coverage: &Coverage,
+    additional_duration: u64,
+) -> Result<Nat, PoolError> {
+    let mut product = PRODUCTS
+        .with(|products| products.borrow().get(&coverage.product_id))
+        .ok_or(PoolError::ProductNotFound)?;
+
+    if product.state != ProductState::Active {
+        return Err(PoolError::ProductNotActive);
+    }
+
+    if has_open_incident(coverage.product_id) {
+        return Err(PoolError::ProductFrozen);
+    }
+
+    if additional_duration == 0 {
+        return Err(PoolError::CoverageDurationTooShort);
+    }
+
+    process_episodes();
+    update_product_allocation(&mut product);
+
+    let current_time = ic_cdk::api::time() / 1_000_000_000;
+
+    if coverage.status != CoverageStatus::Active || current_time >= coverage.end_time {
+        return Err(PoolError::CoverageNotActive);
+    }
+
+    let new_last_covered_episode =
+        (coverage.claim_deadline + additional_duration) / EPISODE_DURATION;
+
+    // The product's allocation already includes this coverage.
+    let required_pool_allocation = product.allocation.clone() * Nat::from(BASIS_POINTS)
+        / Nat::from(product.max_pool_allocation_percent);
+
+    if !verify_product_allocation(new_last_covered_episode, required_pool_allocation) {
+        return Err(PoolError::NotEnoughAssetsToCover);
+    }
+
+    // The extension is priced at the slice of the capacity the coverage already occupies.
+    let allocation_before = if product.allocation > coverage.coverage_amount {
+        product.allocation.clone() - coverage.coverage_amount.clone()
+    } else {
+        Nat::from(0u64)
+    };
+    let capacity = product_capacity(&product, new_last_covered_episode);
+
+    let premium_amount = calculate_premium(
+        &product,
+        additional_duration,
+        &coverage.coverage_amount,
+        &allocation_before,
+        &capacity,
+    );
+    verify_premium(&premium_amount)?;
+
+    Ok(premium_amount)
+}
+
+// Moves the allocation cut and the reward stream of a paid-for extension. The rest of the
+// current reward stream is merged with `reward_amount` into one stream that ends with the
+// extended coverage.
+pub fn apply_coverage_extension(
+    coverage: &mut Coverage,
+    additional_duration: u64,
+    premium_amount: Nat,
+    reward_amount: Nat,
+) {
+    let current_time = ic_cdk::api::time() / 1_000_000_000;
+    let new_end_time = coverage.end_time + additional_duration;
+    let new_claim_deadline = coverage.claim_deadline + additional_duration;
+
+    let coverage_amount = coverage.coverage_amount.clone();
+    remove_allocation_cut(
+        coverage.product_id,
+        coverage.claim_deadline / EPISODE_DURATION,
+        &coverage_amount,
+    );
+    add_allocation_cut(
+        coverage.product_id,
+        new_claim_deadline / EPISODE_DURATION,
+        &coverage_amount,
+    );
+
+    let old_reward_episode = coverage.end_time / EPISODE_DURATION;
+    let old_reward_end_time = (old_reward_episode + 1) * EPISODE_DURATION;
+    let remaining_rewards = coverage.reward_rate.clone()
+        * Nat::from(old_reward_end_time - current_time)
+        / PRECISION_SCALE.clone();
+    remove_reward_rate(old_reward_episode, &coverage.reward_rate);
+
+    let reward_rate = reward_pool_with_duration(
+        remaining_rewards + reward_amount,
+        new_end_time - current_time,
+    );
+
+    coverage.end_time = new_end_time;
+    coverage.claim_deadline = new_claim_deadline;
+    coverage.premium_amount += premium_amount;
+    coverage.reward_rate = reward_rate;
+
+    COVERAGES.with(|coverages| {
+        coverages
+            .borrow_mut()
+            .insert(coverage.coverage_id, coverage.clone());
+    });
+}
+
+// Keeps the coverage id, so claims keep referring to the same policy across renewals.
+#[ic_cdk::update]
+pub async fn extend_coverage(coverage_id: u64, additional_duration: u64) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+
+    let coverage = COVERAGES
+        .with(|coverages| coverages.borrow().get(&coverage_id))
+        .ok_or(PoolError::CoverageNotFound)?;
+
+    if coverage.buyer != caller {
+        return Err(PoolError::NotCoverageBuyer);
+    }
+
+    let premium_amount = prepare_coverage_extension(&coverage, additional_duration)?;
+
+    let max_coverage_duration = PRODUCTS
+        .with(|products| products.borrow().get(&coverage.product_id))
+        .map(|product| product.max_coverage_duration)
+        .unwrap_or(0);
+    let current_time = ic_cdk::api::time() / 1_000_000_000;
+    let new_end_time = coverage.end_time + additional_duration;
+
+    if new_end_time - current_time.max(coverage.start_time) > max_coverage_duration {
+        return Err(PoolError::CoverageDurationTooLong);
+    }
+
+    let purchase_subaccount = get_purchase_subaccount(caller, coverage.product_id);
+    let subaccount_balance = get_subaccount_balance(purchase_subaccount.to_vec()).await?;
+
+    if subaccount_balance < premium_amount {
+        transfer_icrc1(
+            Some(purchase_subaccount.to_vec()),
+            caller,
+            subaccount_balance,
+        )
+        .await?;
+        return Err(PoolError::InsufficientBalance);
+    }
+
+    transfer_icrc1(
+        Some(purchase_subaccount.to_vec()),
+        ic_cdk::api::id(),
+        premium_amount.clone(),
+    )
+    .await?;
+
+    // The coverage may have been cancelled or extended while the premium was being collected.
+    let mut coverage = COVERAGES
+        .with(|coverages| coverages.borrow().get(&coverage_id))
+        .ok_or(PoolError::CoverageNotFound)?;
+
+    let still_extendable = coverage.end_time + additional_duration == new_end_time
+        && prepare_coverage_extension(&coverage, additional_duration).is_ok();
+    if !still_extendable {
+        let net_premium = premium_amount - TRANSFER_FEE.clone();
+        transfer_icrc1(None, caller, net_premium).await?;
+        return Err(PoolError::CoverageNotActive);
+    }
+
+    let net_premium = premium_amount.clone() - TRANSFER_FEE.clone();
+    let commission = accrue_referral_commission(coverage.referrer, &premium_amount, &net_premium);
+    let reward_amount = accrue_premium_fees(&premium_amount, net_premium) - commission;
+    apply_coverage_extension(
+        &mut coverage,
+        additional_duration,
+        premium_amount.clone(),
+        reward_amount,
+    );
+
+    if subaccount_balance > premium_amount.clone() + TRANSFER_FEE.clone() {
+        let refund_amount = subaccount_balance - premium_amount;
+        transfer_icrc1(None, caller, refund_amount).await?;
+    }
 
     Ok(())
 }
@@ -270,7 +728,25 @@ pub fn create_product(
         max_pool_allocation_percent,
         allocation: Nat::from(0u64),
         last_allocation_update: current_time,
-        active: true,
+        state: ProductState::Draft,
+        metadata: ProductMetadata {
+            description: String::new(),
+            category: String::new(),
+            covered_protocols: vec![],
+            risk_rating: RiskRating::Unrated,
+            terms_uri: String::new(),
+            terms_hash: String::new(),
+        },
+        parametric_trigger: None,
+        waiting_period: 0,
+        claim_grace_period: 0,
+        pricing_curve: None,
+        duration_discounts: vec![],
+        volume_discounts: vec![],
+        min_premium: Nat::from(0u64),
+        min_coverage_amount: Nat::from(0u64),
+        max_coverage_per_account: None,
+        max_coverage_per_purchase: None,
     };
 
     PRODUCTS.with(|products| {
@@ -286,7 +762,7 @@ pub fn set_product(
     annual_percent: u64,
     max_coverage_duration: u64,
     max_pool_allocation_percent: u64,
-    active: bool,
+    pricing_curve: Option<PricingCurve>,
 ) -> Result<(), PoolError> {
     let caller = ic_cdk::caller();
     let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
@@ -311,14 +787,326 @@ pub fn set_product(
         return Err(PoolError::InvalidProductParameters);
     }
 
+    if let Some(curve) = &pricing_curve {
+        if curve.base_rate == 0 || curve.kink_utilization > BASIS_POINTS {
+            return Err(PoolError::InvalidProductParameters);
+        }
+    }
+
     let mut product = PRODUCTS
         .with(|products| products.borrow().get(&product_id))
         .ok_or(PoolError::ProductNotFound)?;
 
+    if product.state == ProductState::Retired {
+        return Err(PoolError::ProductRetired);
+    }
+
+    if !fits_active_episodes(
+        product.waiting_period,
+        max_coverage_duration,
+        product.claim_grace_period,
+    ) {
+        return Err(PoolError::InvalidProductParameters);
+    }
+
     product.annual_percent = annual_percent;
     product.max_coverage_duration = max_coverage_duration;
     product.max_pool_allocation_percent = max_pool_allocation_percent;
-    product.active = active;
+    product.pricing_curve = pricing_curve;
+
+    PRODUCTS.with(|products| {
+        products.borrow_mut().insert(product_id, product);
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn set_product_parametric_trigger(
+    product_id: u64,
+    parametric_trigger: Option<ParametricTrigger>,
+) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager {
+        return Err(PoolError::NotPoolManager);
+    }
+
+    if let Some(trigger) = &parametric_trigger {
+        if trigger.trigger_id.trim().is_empty() {
+            return Err(PoolError::InvalidProductParameters);
+        }
+    }
+
+    let mut product = PRODUCTS
+        .with(|products| products.borrow().get(&product_id))
+        .ok_or(PoolError::ProductNotFound)?;
+
+    if product.state == ProductState::Retired {
+        return Err(PoolError::ProductRetired);
+    }
+
+    product.parametric_trigger = parametric_trigger;
+
+    PRODUCTS.with(|products| {
+        products.borrow_mut().insert(product_id, product);
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn set_product_waiting_period(product_id: u64, waiting_period: u64) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager {
+        return Err(PoolError::NotPoolManager);
+    }
+
+    let mut product = PRODUCTS
+        .with(|products| products.borrow().get(&product_id))
+        .ok_or(PoolError::ProductNotFound)?;
+
+    if product.state == ProductState::Retired {
+        return Err(PoolError::ProductRetired);
+    }
+
+    if !fits_active_episodes(
+        waiting_period,
+        product.max_coverage_duration,
+        product.claim_grace_period,
+    ) {
+        return Err(PoolError::InvalidProductParameters);
+    }
+
+    product.waiting_period = waiting_period;
+
+    PRODUCTS.with(|products| {
+        products.borrow_mut().insert(product_id, product);
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn set_product_claim_grace_period(
+    product_id: u64,
+    claim_grace_period: u64,
+) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager {
+        return Err(PoolError::NotPoolManager);
+    }
+
+    let mut product = PRODUCTS
+        .with(|products| products.borrow().get(&product_id))
+        .ok_or(PoolError::ProductNotFound)?;
+
+    if product.state == ProductState::Retired {
+        return Err(PoolError::ProductRetired);
+    }
+
+    if !fits_active_episodes(
+        product.waiting_period,
+        product.max_coverage_duration,
+        claim_grace_period,
+    ) {
+        return Err(PoolError::InvalidProductParameters);
+    }
+
+    product.claim_grace_period = claim_grace_period;
+
+    PRODUCTS.with(|products| {
+        products.borrow_mut().insert(product_id, product);
+    });
+
+    Ok(())
+}
+
+// Thresholds must strictly increase so the applicable tier is unambiguous.
+#[ic_cdk::update]
+pub fn set_product_discounts(
+    product_id: u64,
+    duration_discounts: Vec<DurationDiscount>,
+    volume_discounts: Vec<VolumeDiscount>,
+) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager {
+        return Err(PoolError::NotPoolManager);
+    }
+
+    let mut product = PRODUCTS
+        .with(|products| products.borrow().get(&product_id))
+        .ok_or(PoolError::ProductNotFound)?;
+
+    if product.state == ProductState::Retired {
+        return Err(PoolError::ProductRetired);
+    }
+
+    let durations_sorted = duration_discounts
+        .windows(2)
+        .all(|tiers| tiers[0].min_duration < tiers[1].min_duration);
+    let amounts_sorted = volume_discounts
+        .windows(2)
+        .all(|tiers| tiers[0].min_amount < tiers[1].min_amount);
+    let discounts_valid = duration_discounts
+        .iter()
+        .map(|tier| tier.discount_bps)
+        .chain(volume_discounts.iter().map(|tier| tier.discount_bps))
+        .all(|discount_bps| discount_bps < BASIS_POINTS);
+
+    if !durations_sorted || !amounts_sorted || !discounts_valid {
+        return Err(PoolError::InvalidProductParameters);
+    }
+
+    product.duration_discounts = duration_discounts;
+    product.volume_discounts = volume_discounts;
+
+    PRODUCTS.with(|products| {
+        products.borrow_mut().insert(product_id, product);
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn set_product_minimums(
+    product_id: u64,
+    min_premium: Nat,
+    min_coverage_amount: Nat,
+) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager {
+        return Err(PoolError::NotPoolManager);
+    }
+
+    let mut product = PRODUCTS
+        .with(|products| products.borrow().get(&product_id))
+        .ok_or(PoolError::ProductNotFound)?;
+
+    if product.state == ProductState::Retired {
+        return Err(PoolError::ProductRetired);
+    }
+
+    product.min_premium = min_premium;
+    product.min_coverage_amount = min_coverage_amount;
+
+    PRODUCTS.with(|products| {
+        products.borrow_mut().insert(product_id, product);
+    });
+
+    Ok(())
+}
+
+// Caps are left unset to allow any amount the product's capacity can cover.
+#[ic_cdk::update]
+pub fn set_product_coverage_limits(
+    product_id: u64,
+    max_coverage_per_account: Option<Nat>,
+    max_coverage_per_purchase: Option<Nat>,
+) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager {
+        return Err(PoolError::NotPoolManager);
+    }
+
+    let mut product = PRODUCTS
+        .with(|products| products.borrow().get(&product_id))
+        .ok_or(PoolError::ProductNotFound)?;
+
+    if product.state == ProductState::Retired {
+        return Err(PoolError::ProductRetired);
+    }
+
+    let zero = Nat::from(0u64);
+    let limits_valid = match (&max_coverage_per_account, &max_coverage_per_purchase) {
+        (Some(per_account), Some(per_purchase)) => {
+            *per_purchase > zero && per_purchase <= per_account
+        }
+        (Some(limit), None) | (None, Some(limit)) => *limit > zero,
+        (None, None) => true,
+    };
+
+    if !limits_valid {
+        return Err(PoolError::InvalidProductParameters);
+    }
+
+    product.max_coverage_per_account = max_coverage_per_account;
+    product.max_coverage_per_purchase = max_coverage_per_purchase;
+
+    PRODUCTS.with(|products| {
+        products.borrow_mut().insert(product_id, product);
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn set_product_state(product_id: u64, state: ProductState) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager {
+        return Err(PoolError::NotPoolManager);
+    }
+
+    let mut product = PRODUCTS
+        .with(|products| products.borrow().get(&product_id))
+        .ok_or(PoolError::ProductNotFound)?;
+
+    if product.state == ProductState::Retired {
+        return Err(PoolError::ProductRetired);
+    }
+
+    let allowed = matches!(
+        (product.state, state),
+        (ProductState::Draft, ProductState::Active)
+            | (ProductState::Active, ProductState::Paused)
+            | (ProductState::Paused, ProductState::Active)
+            | (_, ProductState::Retired)
+    );
+
+    if !allowed {
+        return Err(PoolError::InvalidStateTransition);
+    }
+
+    product.state = state;
+
+    PRODUCTS.with(|products| {
+        products.borrow_mut().insert(product_id, product);
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn set_product_metadata(product_id: u64, metadata: ProductMetadata) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager {
+        return Err(PoolError::NotPoolManager);
+    }
+
+    let mut product = PRODUCTS
+        .with(|products| products.borrow().get(&product_id))
+        .ok_or(PoolError::ProductNotFound)?;
+
+    if product.state == ProductState::Retired {
+        return Err(PoolError::ProductRetired);
+    }
+
+    product.metadata = metadata;
 
     PRODUCTS.with(|products| {
         products.borrow_mut().insert(product_id, product);
@@ -328,11 +1116,27 @@ pub fn set_product(
 }
 
 #[ic_cdk::query]
-pub fn get_products() -> Vec<Product> {
+pub fn get_product(product_id: u64) -> Option<Product> {
+    PRODUCTS.with(|products| {
+        products.borrow().get(&product_id).map(|product| {
+            let mut updated_product = product.clone();
+            updated_product.allocation = compute_current_product_allocation(&product);
+            updated_product
+        })
+    })
+}
+
+// Without a state filter every product but the retired ones is listed.
+#[ic_cdk::query]
+pub fn get_products(state: Option<ProductState>) -> Vec<Product> {
     PRODUCTS.with(|products| {
         products
             .borrow()
             .iter()
+            .filter(|(_, product)| match state {
+                Some(state) => product.state == state,
+                None => product.state != ProductState::Retired,
+            })
             .map(|(_, product)| {
                 let mut updated_product = product.clone();
                 updated_product.allocation = compute_current_product_allocation(&product);
@@ -1,4 +1,5 @@
 use crate::storage::*;
+use crate::subscriptions::process_subscriptions;
 use crate::types::{Episode, StorableNat};
 use crate::{EPISODE_DURATION, MAX_ACTIVE_EPISODES};
 use candid::Nat;
@@ -133,6 +134,7 @@ pub fn setup_episode_timer() {
 
     ic_cdk_timers::set_timer(std::time::Duration::from_secs(time_to_next_episode), || {
         process_episodes();
+        ic_cdk::spawn(process_subscriptions());
         setup_episode_timer();
     });
 }
\ No newline at end of file
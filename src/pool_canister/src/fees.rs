@@ -0,0 +1,183 @@
+use crate::ledger::transfer_icrc1;
+use crate::pricing::BASIS_POINTS;
+use crate::referrals::max_commission_bps;
+use crate::storage::*;
+use crate::types::{FeeConfig, PoolAnalytics, PoolError, StorableNat};
+use crate::TRANSFER_FEE;
+use candid::{Nat, Principal};
+use ic_stable_structures::StableCell;
+use std::cell::RefCell;
+use std::thread::LocalKey;
+
+type NatCell = RefCell<StableCell<StorableNat, Memory>>;
+
+fn add_to(cell: &'static LocalKey<NatCell>, amount: &Nat) {
+    cell.with(|cell| {
+        let current = cell.borrow().get().clone().0;
+        cell.borrow_mut()
+            .set(StorableNat(current + amount.clone()))
+            .ok();
+    });
+}
+
+// Takes the protocol and pool manager shares out of a premium's net amount and returns what is
+// left to stream to LPs.
+pub fn accrue_premium_fees(premium_amount: &Nat, net_amount: Nat) -> Nat {
+    let protocol_fee_bps = PROTOCOL_FEE_BPS.with(|cell| *cell.borrow().get());
+    let manager_fee_bps = MANAGER_FEE_BPS.with(|cell| *cell.borrow().get());
+
+    let protocol_fee = net_amount.clone() * Nat::from(protocol_fee_bps) / Nat::from(BASIS_POINTS);
+    let manager_fee = net_amount.clone() * Nat::from(manager_fee_bps) / Nat::from(BASIS_POINTS);
+
+    add_to(&TOTAL_PREMIUMS_COLLECTED, premium_amount);
+    add_to(&PROTOCOL_FEES_ACCRUED, &protocol_fee);
+    add_to(&MANAGER_FEES_ACCRUED, &manager_fee);
+
+    net_amount - protocol_fee - manager_fee
+}
+
+// Whatever the referrer, part of every premium must still reach LPs.
+pub fn fees_fit(protocol_fee_bps: u64, manager_fee_bps: u64, commission_bps: u64) -> bool {
+    protocol_fee_bps + manager_fee_bps + commission_bps < BASIS_POINTS
+}
+
+#[ic_cdk::update]
+pub fn set_protocol_owner(new_owner: Principal) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+    let protocol_owner = PROTOCOL_OWNER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != protocol_owner {
+        return Err(PoolError::NotOwner);
+    }
+
+    PROTOCOL_OWNER_PRINCIPAL.with(|cell| {
+        cell.borrow_mut().set(new_owner).ok();
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::query]
+pub fn get_protocol_owner() -> Principal {
+    PROTOCOL_OWNER_PRINCIPAL.with(|cell| cell.borrow().get().clone())
+}
+
+#[ic_cdk::update]
+pub fn set_protocol_fee(
+    protocol_treasury: Principal,
+    protocol_fee_bps: u64,
+) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+    let protocol_owner = PROTOCOL_OWNER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != protocol_owner {
+        return Err(PoolError::NotOwner);
+    }
+
+    let manager_fee_bps = MANAGER_FEE_BPS.with(|cell| *cell.borrow().get());
+    if !fees_fit(protocol_fee_bps, manager_fee_bps, max_commission_bps()) {
+        return Err(PoolError::InvalidFee);
+    }
+
+    PROTOCOL_TREASURY_PRINCIPAL.with(|cell| {
+        cell.borrow_mut().set(protocol_treasury).ok();
+    });
+    PROTOCOL_FEE_BPS.with(|cell| {
+        cell.borrow_mut().set(protocol_fee_bps).ok();
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn set_manager_fee(manager_fee_bps: u64) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager {
+        return Err(PoolError::NotPoolManager);
+    }
+
+    let protocol_fee_bps = PROTOCOL_FEE_BPS.with(|cell| *cell.borrow().get());
+    if !fees_fit(protocol_fee_bps, manager_fee_bps, max_commission_bps()) {
+        return Err(PoolError::InvalidFee);
+    }
+
+    MANAGER_FEE_BPS.with(|cell| {
+        cell.borrow_mut().set(manager_fee_bps).ok();
+    });
+
+    Ok(())
+}
+
+// Pays out the whole accrued balance, restoring it if the transfer fails.
+async fn claim_fees(
+    cell: &'static LocalKey<NatCell>,
+    recipient: Principal,
+) -> Result<Nat, PoolError> {
+    let amount = cell.with(|cell| cell.borrow().get().clone().0);
+
+    if amount <= TRANSFER_FEE.clone() {
+        return Err(PoolError::InsufficientBalance);
+    }
+
+    cell.with(|cell| {
+        cell.borrow_mut().set(StorableNat(Nat::from(0u64))).ok();
+    });
+
+    if let Err(error) = transfer_icrc1(None, recipient, amount.clone()).await {
+        add_to(cell, &amount);
+        return Err(error);
+    }
+
+    Ok(amount)
+}
+
+#[ic_cdk::update]
+pub async fn claim_protocol_fees() -> Result<Nat, PoolError> {
+    let caller = ic_cdk::caller();
+    let treasury = PROTOCOL_TREASURY_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != treasury {
+        return Err(PoolError::NotFeeRecipient);
+    }
+
+    claim_fees(&PROTOCOL_FEES_ACCRUED, caller).await
+}
+
+#[ic_cdk::update]
+pub async fn claim_manager_fees() -> Result<Nat, PoolError> {
+    let caller = ic_cdk::caller();
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager {
+        return Err(PoolError::NotFeeRecipient);
+    }
+
+    claim_fees(&MANAGER_FEES_ACCRUED, caller).await
+}
+
+#[ic_cdk::query]
+pub fn get_fee_config() -> FeeConfig {
+    FeeConfig {
+        protocol_treasury: PROTOCOL_TREASURY_PRINCIPAL.with(|cell| cell.borrow().get().clone()),
+        protocol_fee_bps: PROTOCOL_FEE_BPS.with(|cell| *cell.borrow().get()),
+        manager_fee_bps: MANAGER_FEE_BPS.with(|cell| *cell.borrow().get()),
+    }
+}
+
+#[ic_cdk::query]
+pub fn get_pool_analytics() -> PoolAnalytics {
+    let pool_state = POOL_STATE.with(|state| state.borrow().get().clone());
+
+    PoolAnalytics {
+        total_assets: pool_state.total_assets,
+        total_shares: pool_state.total_shares,
+        total_cover_allocation: TOTAL_COVER_ALLOCATION.with(|cell| cell.borrow().get().clone().0),
+        pool_reward_rate: POOL_REWARD_RATE.with(|cell| cell.borrow().get().clone().0),
+        total_premiums_collected: TOTAL_PREMIUMS_COLLECTED
+            .with(|cell| cell.borrow().get().clone().0),
+        protocol_fees_accrued: PROTOCOL_FEES_ACCRUED.with(|cell| cell.borrow().get().clone().0),
+        manager_fees_accrued: MANAGER_FEES_ACCRUED.with(|cell| cell.borrow().get().clone().0),
+    }
+}
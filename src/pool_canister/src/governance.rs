@@ -1,10 +1,13 @@
 use crate::episodes::get_current_episode;
 use crate::ledger::transfer_icrc1;
 use crate::storage::*;
-use crate::types::PoolError;
+use crate::types::{PoolError, SlashRecord, SlashStatus};
 use crate::MAX_ACTIVE_EPISODES;
 use candid::{Nat, Principal};
 
+// A transfer still marked in progress after this long was lost to a trap and needs resolving.
+pub const SLASH_RESOLUTION_DELAY: u64 = 60 * 60;
+
 #[ic_cdk::update]
 pub fn set_executor_principal(executor: Principal) -> Result<(), PoolError> {
     let caller = ic_cdk::api::caller();
@@ -36,7 +39,7 @@ pub fn set_pool_manager_principal(pool_manager: Principal) -> Result<(), PoolErr
 }
 
 #[ic_cdk::update]
-pub async fn slash(receiver: Principal, amount: Nat) -> Result<(), PoolError> {
+pub async fn slash(claim_id: u64, receiver: Principal, amount: Nat) -> Result<(), PoolError> {
     let caller = ic_cdk::api::caller();
     let executor_principal = EXECUTOR_PRINCIPAL.with(|cell| cell.borrow().get().clone());
 
@@ -44,6 +47,15 @@ pub async fn slash(receiver: Principal, amount: Nat) -> Result<(), PoolError> {
         return Err(PoolError::NotSlashingExecutor);
     }
 
+    // The claim id is an idempotency key: a repeated call never slashes twice.
+    if let Some(record) = SLASH_RECORDS.with(|records| records.borrow().get(&claim_id)) {
+        return match record.status {
+            SlashStatus::Completed => Ok(()),
+            SlashStatus::InProgress => Err(PoolError::SlashInProgress),
+            SlashStatus::TransferFailed => transfer_slashed_assets(claim_id, record).await,
+        };
+    }
+
     let current_episode = get_current_episode();
 
     // Check if amount to slash exceeds pool's active stake
@@ -78,11 +90,83 @@ pub async fn slash(receiver: Principal, amount: Nat) -> Result<(), PoolError> {
         accumulated_slashed
     });
 
-    transfer_icrc1(None, receiver, accumulated_slashed).await?;
+    let record = SlashRecord {
+        receiver,
+        amount,
+        slashed_amount: accumulated_slashed,
+        status: SlashStatus::InProgress,
+        created_at: ic_cdk::api::time() / 1_000_000_000,
+        attempted_at: ic_cdk::api::time() / 1_000_000_000,
+    };
+
+    transfer_slashed_assets(claim_id, record).await
+}
+
+async fn transfer_slashed_assets(claim_id: u64, mut record: SlashRecord) -> Result<(), PoolError> {
+    record.status = SlashStatus::InProgress;
+    record.attempted_at = ic_cdk::api::time() / 1_000_000_000;
+    SLASH_RECORDS.with(|records| {
+        records.borrow_mut().insert(claim_id, record.clone());
+    });
+
+    let transfer_result =
+        transfer_icrc1(None, record.receiver, record.slashed_amount.clone()).await;
+
+    record.status = if transfer_result.is_ok() {
+        SlashStatus::Completed
+    } else {
+        SlashStatus::TransferFailed
+    };
+    SLASH_RECORDS.with(|records| {
+        records.borrow_mut().insert(claim_id, record);
+    });
+
+    transfer_result.map(|_| ())
+}
+
+// If the canister trapped while transferring, the record stays `InProgress` and every retry is
+// refused. After checking the ledger, the pool manager records whether the transfer went out;
+// a failed one is retried by the next slash call.
+#[ic_cdk::update]
+pub fn resolve_slash(claim_id: u64, transferred: bool) -> Result<(), PoolError> {
+    let caller = ic_cdk::api::caller();
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager {
+        return Err(PoolError::NotPoolManager);
+    }
+
+    let mut record = SLASH_RECORDS
+        .with(|records| records.borrow().get(&claim_id))
+        .ok_or(PoolError::SlashRecordNotFound)?;
+
+    if record.status != SlashStatus::InProgress {
+        return Err(PoolError::SlashNotInProgress);
+    }
+
+    let current_time = ic_cdk::api::time() / 1_000_000_000;
+    if current_time < record.attempted_at + SLASH_RESOLUTION_DELAY {
+        return Err(PoolError::TimelockNotExpired);
+    }
+
+    record.status = if transferred {
+        SlashStatus::Completed
+    } else {
+        SlashStatus::TransferFailed
+    };
+
+    SLASH_RECORDS.with(|records| {
+        records.borrow_mut().insert(claim_id, record);
+    });
 
     Ok(())
 }
 
+#[ic_cdk::query]
+pub fn get_slash_record(claim_id: u64) -> Option<SlashRecord> {
+    SLASH_RECORDS.with(|records| records.borrow().get(&claim_id))
+}
+
 #[ic_cdk::query]
 pub fn get_executor_principal() -> Principal {
     EXECUTOR_PRINCIPAL.with(|cell| cell.borrow().get().clone())
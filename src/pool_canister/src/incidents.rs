@@ -0,0 +1,132 @@
+use crate::storage::*;
+use crate::types::{Incident, IncidentSeverity, IncidentStatus, PoolError};
+use candid::Principal;
+
+// The executor is the claim canister, which forwards the incidents its oracles report.
+fn ensure_incident_reporter(caller: Principal) -> Result<(), PoolError> {
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+    let executor = EXECUTOR_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+    let oracle = ORACLE_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager
+        && caller != executor
+        && (oracle == Principal::anonymous() || caller != oracle)
+    {
+        return Err(PoolError::NotIncidentReporter);
+    }
+
+    Ok(())
+}
+
+// Purchases of a product stay frozen while any of its incidents is open.
+pub fn has_open_incident(product_id: u64) -> bool {
+    INCIDENTS.with(|incidents| {
+        incidents.borrow().iter().any(|(_, incident)| {
+            incident.product_id == product_id && incident.status == IncidentStatus::Open
+        })
+    })
+}
+
+#[ic_cdk::update]
+pub fn set_oracle_principal(oracle: Principal) -> Result<(), PoolError> {
+    let caller = ic_cdk::api::caller();
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager {
+        return Err(PoolError::NotPoolManager);
+    }
+
+    ORACLE_PRINCIPAL.with(|cell| {
+        cell.borrow_mut().set(oracle).ok();
+    });
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn report_incident(
+    product_id: u64,
+    incident_time: u64,
+    severity: IncidentSeverity,
+) -> Result<u64, PoolError> {
+    let caller = ic_cdk::api::caller();
+    ensure_incident_reporter(caller)?;
+
+    if !PRODUCTS.with(|products| products.borrow().contains_key(&product_id)) {
+        return Err(PoolError::ProductNotFound);
+    }
+
+    let current_time = ic_cdk::api::time() / 1_000_000_000;
+
+    if incident_time > current_time {
+        return Err(PoolError::InvalidIncidentTime);
+    }
+
+    let incident_id = INCIDENT_COUNTER.with(|counter| {
+        let current = counter.borrow().get().clone();
+        let new_counter = current + 1;
+        counter.borrow_mut().set(new_counter).ok();
+        current
+    });
+
+    let incident = Incident {
+        incident_id,
+        product_id,
+        incident_time,
+        severity,
+        status: IncidentStatus::Open,
+        reported_by: caller,
+        reported_at: current_time,
+        resolved_at: None,
+    };
+
+    INCIDENTS.with(|incidents| {
+        incidents.borrow_mut().insert(incident_id, incident);
+    });
+
+    Ok(incident_id)
+}
+
+#[ic_cdk::update]
+pub fn resolve_incident(incident_id: u64) -> Result<(), PoolError> {
+    let caller = ic_cdk::api::caller();
+    ensure_incident_reporter(caller)?;
+
+    let mut incident = INCIDENTS
+        .with(|incidents| incidents.borrow().get(&incident_id))
+        .ok_or(PoolError::IncidentNotFound)?;
+
+    if incident.status == IncidentStatus::Resolved {
+        return Err(PoolError::IncidentAlreadyResolved);
+    }
+
+    incident.status = IncidentStatus::Resolved;
+    incident.resolved_at = Some(ic_cdk::api::time() / 1_000_000_000);
+
+    INCIDENTS.with(|incidents| {
+        incidents.borrow_mut().insert(incident_id, incident);
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::query]
+pub fn get_oracle_principal() -> Principal {
+    ORACLE_PRINCIPAL.with(|cell| cell.borrow().get().clone())
+}
+
+#[ic_cdk::query]
+pub fn get_incident(incident_id: u64) -> Option<Incident> {
+    INCIDENTS.with(|incidents| incidents.borrow().get(&incident_id))
+}
+
+#[ic_cdk::query]
+pub fn get_product_incidents(product_id: u64) -> Vec<Incident> {
+    INCIDENTS.with(|incidents| {
+        incidents
+            .borrow()
+            .iter()
+            .map(|(_, incident)| incident)
+            .filter(|incident| incident.product_id == product_id)
+            .collect()
+    })
+}
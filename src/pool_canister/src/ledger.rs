@@ -1,5 +1,7 @@
 use crate::storage::TOKEN_ID;
-use crate::types::{Account, PoolError, TransferArg, TransferError};
+use crate::types::{
+    Account, PoolError, TransferArg, TransferError, TransferFromArgs, TransferFromError,
+};
 use crate::TRANSFER_FEE;
 use candid::{Nat, Principal};
 use ic_cdk::api::call::call;
@@ -88,3 +90,39 @@ pub async fn transfer_icrc1(
         _ => Err(PoolError::TransferFailed),
     }
 }
+
+// Pulls `amount` from `from`'s main account into the pool's main account. The ledger fee is
+// charged to `from` on top of `amount`, so the allowance must cover both.
+pub async fn transfer_from_icrc2(from: Principal, amount: Nat) -> Result<Nat, PoolError> {
+    let ledger_principal = get_ledger_principal()?;
+
+    let transfer_from_args = (TransferFromArgs {
+        spender_subaccount: None,
+        from: Account {
+            owner: from,
+            subaccount: None,
+        },
+        to: Account {
+            owner: ic_cdk::api::id(),
+            subaccount: None,
+        },
+        amount,
+        fee: Some(TRANSFER_FEE.clone()),
+        memo: None,
+        created_at_time: None,
+    },);
+
+    let transfer_result: Result<(Result<Nat, TransferFromError>,), _> =
+        call(ledger_principal, "icrc2_transfer_from", transfer_from_args).await;
+
+    match transfer_result {
+        Ok((Ok(block_index),)) => Ok(block_index),
+        Ok((Err(TransferFromError::InsufficientAllowance { .. }),)) => {
+            Err(PoolError::InsufficientAllowance)
+        }
+        Ok((Err(TransferFromError::InsufficientFunds { .. }),)) => {
+            Err(PoolError::InsufficientBalance)
+        }
+        _ => Err(PoolError::TransferFailed),
+    }
+}
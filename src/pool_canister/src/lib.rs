@@ -13,20 +13,29 @@ lazy_static! {
 pub mod coverage;
 pub mod deposit;
 pub mod episodes;
+pub mod fees;
 pub mod governance;
+pub mod incidents;
 pub mod ledger;
+pub mod pricing;
+pub mod referrals;
 pub mod rewards;
 pub mod storage;
+pub mod subscriptions;
 pub mod types;
 
 pub use types::{
-    Account, Coverage, Deposit, Episode, PoolError, PoolState, Product, StorableNat,
-    TransferArg, TransferError, UserCoverages, UserDepositInfo, UserDeposits,
+    Account, Coverage, CoverageQuote, CoverageStatus, Deposit, DurationDiscount, Episode,
+    FeeConfig, Incident, IncidentSeverity, IncidentStatus, ParametricTrigger, PoolAnalytics,
+    PoolError, PoolState, PricingCurve, Product, ProductMetadata, ProductState, Referrer,
+    RiskRating, SlashRecord, SlashStatus, StorableNat, Subscription, SubscriptionStatus,
+    TransferArg, TransferError, UserCoverages, UserDepositInfo, UserDeposits, VolumeDiscount,
 };
 
 pub use ledger::{get_purchase_subaccount, get_subaccount_balance, transfer_icrc1};
 use storage::*;
 
+use coverage::index_covered_account_coverages;
 use episodes::setup_episode_timer;
 
 #[ic_cdk::init]
@@ -48,6 +57,24 @@ pub fn init(token_id: Principal, executor: Principal,pool_manager: Principal) {
         cell.borrow_mut().set(pool_manager).ok();
     });
 
+    // The protocol deploys its pools, so the deployer governs the protocol fee.
+    PROTOCOL_OWNER_PRINCIPAL.with(|cell| {
+        cell.borrow_mut().set(ic_cdk::caller()).ok();
+    });
+
+    setup_episode_timer();
+}
+
+#[ic_cdk::post_upgrade]
+pub fn post_upgrade() {
+    // Pools deployed before the protocol owner existed are handed to the upgrading controller.
+    PROTOCOL_OWNER_PRINCIPAL.with(|cell| {
+        if *cell.borrow().get() == Principal::anonymous() {
+            cell.borrow_mut().set(ic_cdk::caller()).ok();
+        }
+    });
+
+    index_covered_account_coverages();
     setup_episode_timer();
 }
 
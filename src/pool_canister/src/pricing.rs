@@ -0,0 +1,194 @@
+use crate::coverage::{
+    available_pool_allocation, compute_current_product_allocation, remaining_account_coverage,
+    verify_product_allocation,
+};
+use crate::incidents::has_open_incident;
+use crate::storage::*;
+use crate::types::{CoverageQuote, PoolError, PricingCurve, Product, ProductState};
+use crate::{EPISODE_DURATION, TRANSFER_FEE};
+use candid::{Nat, Principal};
+
+pub const BASIS_POINTS: u64 = 10_000;
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+// The most a product may cover until `last_covered_episode`.
+pub fn product_capacity(product: &Product, last_covered_episode: u64) -> Nat {
+    available_pool_allocation(last_covered_episode) * Nat::from(product.max_pool_allocation_percent)
+        / Nat::from(BASIS_POINTS)
+}
+
+// Prices `coverage_amount` as the slice of the product's capacity between `allocation_before`
+// and `allocation_before + coverage_amount`, integrating the pricing curve over that slice.
+pub fn calculate_premium(
+    product: &Product,
+    coverage_duration: u64,
+    coverage_amount: &Nat,
+    allocation_before: &Nat,
+    capacity: &Nat,
+) -> Nat {
+    let weighted_rate = match &product.pricing_curve {
+        Some(curve) => curve_weighted_rate(curve, coverage_amount, allocation_before, capacity),
+        None => Nat::from(product.annual_percent) * coverage_amount.clone(),
+    };
+
+    let discount_factor = Nat::from(BASIS_POINTS - duration_discount(product, coverage_duration))
+        * Nat::from(BASIS_POINTS - volume_discount(product, coverage_amount));
+
+    // Rounded up, so dust never goes in the buyer's favor.
+    let denominator =
+        Nat::from(SECONDS_PER_YEAR) * Nat::from(BASIS_POINTS * BASIS_POINTS * BASIS_POINTS);
+    let premium = (Nat::from(coverage_duration) * weighted_rate * discount_factor
+        + denominator.clone()
+        - Nat::from(1u64))
+        / denominator;
+
+    premium.max(product.min_premium.clone())
+}
+
+// The premium has to leave something for LPs once the ledger fee is paid.
+pub fn verify_premium(premium_amount: &Nat) -> Result<(), PoolError> {
+    if *premium_amount <= TRANSFER_FEE.clone() {
+        return Err(PoolError::PremiumTooLow);
+    }
+
+    Ok(())
+}
+
+pub fn duration_discount(product: &Product, coverage_duration: u64) -> u64 {
+    product
+        .duration_discounts
+        .iter()
+        .rev()
+        .find(|tier| tier.min_duration <= coverage_duration)
+        .map(|tier| tier.discount_bps)
+        .unwrap_or(0)
+}
+
+pub fn volume_discount(product: &Product, coverage_amount: &Nat) -> u64 {
+    product
+        .volume_discounts
+        .iter()
+        .rev()
+        .find(|tier| tier.min_amount <= *coverage_amount)
+        .map(|tier| tier.discount_bps)
+        .unwrap_or(0)
+}
+
+// The integral of the annual rate over the covered allocation, in basis points times tokens.
+fn curve_weighted_rate(
+    curve: &PricingCurve,
+    coverage_amount: &Nat,
+    allocation_before: &Nat,
+    capacity: &Nat,
+) -> Nat {
+    let base = Nat::from(curve.base_rate) * coverage_amount.clone();
+
+    if *capacity == Nat::from(0u64) {
+        return base;
+    }
+
+    let kink = capacity.clone() * Nat::from(curve.kink_utilization) / Nat::from(BASIS_POINTS);
+    let allocation_after = allocation_before.clone() + coverage_amount.clone();
+
+    if allocation_after <= kink {
+        return base;
+    }
+
+    let above_before = if *allocation_before > kink {
+        allocation_before.clone() - kink.clone()
+    } else {
+        Nat::from(0u64)
+    };
+    let above_after = allocation_after - kink;
+
+    base + Nat::from(curve.slope_above_kink)
+        * (above_after.clone() * above_after - above_before.clone() * above_before)
+        / (Nat::from(2u64) * capacity.clone())
+}
+
+// Runs the same checks as `purchase_coverage` up to the premium payment, so frontends know
+// what a purchase will cost and whether it will go through before funding the subaccount.
+// Per-account caps are only applied when the covered account is given.
+#[ic_cdk::query]
+pub fn quote_coverage(
+    product_id: u64,
+    coverage_duration: u64,
+    coverage_amount: Nat,
+    covered_account: Option<Principal>,
+) -> Result<CoverageQuote, PoolError> {
+    let product = PRODUCTS
+        .with(|products| products.borrow().get(&product_id))
+        .ok_or(PoolError::ProductNotFound)?;
+
+    // Durations too large to add up could never be bought anyway.
+    let current_time = ic_cdk::api::time() / 1_000_000_000;
+    let claim_deadline = current_time
+        .checked_add(product.waiting_period)
+        .and_then(|time| time.checked_add(coverage_duration))
+        .and_then(|time| time.checked_add(product.claim_grace_period))
+        .ok_or(PoolError::CoverageDurationTooLong)?;
+    let last_covered_episode = claim_deadline / EPISODE_DURATION;
+
+    let product_allocation = compute_current_product_allocation(&product);
+    let capacity = product_capacity(&product, last_covered_episode);
+    let mut max_coverage_amount = if capacity > product_allocation {
+        capacity.clone() - product_allocation.clone()
+    } else {
+        Nat::from(0u64)
+    };
+    if let Some(max_coverage_per_purchase) = &product.max_coverage_per_purchase {
+        max_coverage_amount = max_coverage_amount.min(max_coverage_per_purchase.clone());
+    }
+    let remaining_account_coverage = covered_account.and_then(|covered_account| {
+        remaining_account_coverage(&product, covered_account, current_time)
+    });
+    if let Some(remaining_account_coverage) = &remaining_account_coverage {
+        max_coverage_amount = max_coverage_amount.min(remaining_account_coverage.clone());
+    }
+
+    let premium_amount = calculate_premium(
+        &product,
+        coverage_duration,
+        &coverage_amount,
+        &product_allocation,
+        &capacity,
+    );
+
+    let required_pool_allocation = (coverage_amount.clone() + product_allocation)
+        * Nat::from(BASIS_POINTS)
+        / Nat::from(product.max_pool_allocation_percent);
+
+    let error = if product.state != ProductState::Active {
+        Some(PoolError::ProductNotActive)
+    } else if has_open_incident(product_id) {
+        Some(PoolError::ProductFrozen)
+    } else if coverage_duration > product.max_coverage_duration {
+        Some(PoolError::CoverageDurationTooLong)
+    } else if coverage_duration < EPISODE_DURATION {
+        Some(PoolError::CoverageDurationTooShort)
+    } else if coverage_amount < product.min_coverage_amount {
+        Some(PoolError::CoverageAmountTooSmall)
+    } else if product
+        .max_coverage_per_purchase
+        .as_ref()
+        .is_some_and(|max_coverage_per_purchase| coverage_amount > *max_coverage_per_purchase)
+    {
+        Some(PoolError::CoverageAmountTooLarge)
+    } else if remaining_account_coverage.is_some_and(|remaining| coverage_amount > remaining) {
+        Some(PoolError::AccountCoverageLimitExceeded)
+    } else if !verify_product_allocation(last_covered_episode, required_pool_allocation) {
+        Some(PoolError::NotEnoughAssetsToCover)
+    } else {
+        verify_premium(&premium_amount).err()
+    };
+
+    Ok(CoverageQuote {
+        premium_amount: premium_amount.clone(),
+        duration_discount_bps: duration_discount(&product, coverage_duration),
+        volume_discount_bps: volume_discount(&product, &coverage_amount),
+        transfer_fee: TRANSFER_FEE.clone(),
+        required_funding: premium_amount,
+        max_coverage_amount,
+        error,
+    })
+}
@@ -0,0 +1,130 @@
+use crate::fees::fees_fit;
+use crate::ledger::transfer_icrc1;
+use crate::pricing::BASIS_POINTS;
+use crate::storage::*;
+use crate::types::{PoolError, Referrer};
+use crate::TRANSFER_FEE;
+use candid::{Nat, Principal};
+
+pub fn max_commission_bps() -> u64 {
+    REFERRERS.with(|referrers| {
+        referrers
+            .borrow()
+            .iter()
+            .map(|(_, referrer)| referrer.commission_bps)
+            .max()
+            .unwrap_or(0)
+    })
+}
+
+// Registering an existing referrer only changes its rate; its earnings are kept.
+#[ic_cdk::update]
+pub fn register_referrer(referrer: Principal, commission_bps: u64) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+    let pool_manager = POOL_MANAGER_PRINCIPAL.with(|cell| cell.borrow().get().clone());
+
+    if caller != pool_manager {
+        return Err(PoolError::NotPoolManager);
+    }
+
+    let protocol_fee_bps = PROTOCOL_FEE_BPS.with(|cell| *cell.borrow().get());
+    let manager_fee_bps = MANAGER_FEE_BPS.with(|cell| *cell.borrow().get());
+
+    if !fees_fit(protocol_fee_bps, manager_fee_bps, commission_bps) {
+        return Err(PoolError::InvalidFee);
+    }
+
+    REFERRERS.with(|referrers| {
+        let mut referrers_ref = referrers.borrow_mut();
+        let mut entry = referrers_ref.get(&referrer).unwrap_or(Referrer {
+            referrer,
+            commission_bps,
+            commissions_accrued: Nat::from(0u64),
+            commissions_earned: Nat::from(0u64),
+            premium_volume: Nat::from(0u64),
+        });
+        entry.commission_bps = commission_bps;
+        referrers_ref.insert(referrer, entry);
+    });
+
+    Ok(())
+}
+
+pub fn verify_referrer(referrer: Option<Principal>) -> Result<(), PoolError> {
+    match referrer {
+        Some(referrer)
+            if !REFERRERS.with(|referrers| referrers.borrow().contains_key(&referrer)) =>
+        {
+            Err(PoolError::ReferrerNotFound)
+        }
+        _ => Ok(()),
+    }
+}
+
+// Credits the referrer's commission on `net_amount` and returns it, so it can be deducted
+// before the premium is streamed to LPs.
+pub fn accrue_referral_commission(
+    referrer: Option<Principal>,
+    premium_amount: &Nat,
+    net_amount: &Nat,
+) -> Nat {
+    let referrer = match referrer {
+        Some(referrer) => referrer,
+        None => return Nat::from(0u64),
+    };
+
+    REFERRERS.with(|referrers| {
+        let mut referrers_ref = referrers.borrow_mut();
+        let mut entry = match referrers_ref.get(&referrer) {
+            Some(entry) => entry,
+            None => return Nat::from(0u64),
+        };
+
+        let commission =
+            net_amount.clone() * Nat::from(entry.commission_bps) / Nat::from(BASIS_POINTS);
+        entry.commissions_accrued += commission.clone();
+        entry.commissions_earned += commission.clone();
+        entry.premium_volume += premium_amount.clone();
+        referrers_ref.insert(referrer, entry);
+
+        commission
+    })
+}
+
+#[ic_cdk::update]
+pub async fn withdraw_referral_commissions() -> Result<Nat, PoolError> {
+    let caller = ic_cdk::caller();
+
+    let mut entry = REFERRERS
+        .with(|referrers| referrers.borrow().get(&caller))
+        .ok_or(PoolError::ReferrerNotFound)?;
+
+    let amount = entry.commissions_accrued.clone();
+
+    if amount <= TRANSFER_FEE.clone() {
+        return Err(PoolError::InsufficientBalance);
+    }
+
+    entry.commissions_accrued = Nat::from(0u64);
+    REFERRERS.with(|referrers| {
+        referrers.borrow_mut().insert(caller, entry);
+    });
+
+    if let Err(error) = transfer_icrc1(None, caller, amount.clone()).await {
+        REFERRERS.with(|referrers| {
+            let mut referrers_ref = referrers.borrow_mut();
+            if let Some(mut entry) = referrers_ref.get(&caller) {
+                entry.commissions_accrued += amount;
+                referrers_ref.insert(caller, entry);
+            }
+        });
+        return Err(error);
+    }
+
+    Ok(amount)
+}
+
+#[ic_cdk::query]
+pub fn get_referrer(referrer: Principal) -> Option<Referrer> {
+    REFERRERS.with(|referrers| referrers.borrow().get(&referrer))
+}
@@ -84,7 +84,8 @@ pub async fn reward_pool() -> Result<(), PoolError> {
     Ok(())
 }
 
-pub fn reward_pool_with_duration(amount: Nat, coverage_duration: u64) {
+// Returns the reward rate added to the pool, so the stream can be reversed later.
+pub fn reward_pool_with_duration(amount: Nat, coverage_duration: u64) -> Nat {
     let current_time = ic_cdk::api::time() / 1_000_000_000;
     let last_reward_episode = (current_time + coverage_duration) / EPISODE_DURATION;
     let reward_duration = (last_reward_episode + 1) * EPISODE_DURATION - current_time;
@@ -108,7 +109,9 @@ pub fn reward_pool_with_duration(amount: Nat, coverage_duration: u64) {
             coverage_decrease: Nat::from(0u64),
             acc_reward_per_share_on_expire: Nat::from(0u64),
         });
-        episode.reward_decrease += reward_rate_increase;
+        episode.reward_decrease += reward_rate_increase.clone();
         episodes_ref.insert(target_episode_id, episode);
     });
+
+    reward_rate_increase
 }
\ No newline at end of file
@@ -3,7 +3,10 @@ use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemor
 use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, StableCell};
 use std::cell::RefCell;
 
-use crate::types::{Coverage, Deposit, Episode, PoolState, Product, StorableNat, UserCoverages, UserDeposits};
+use crate::types::{
+    Coverage, Deposit, Episode, Incident, PoolState, Product, Referrer, SlashRecord, StorableNat,
+    Subscription, UserCoverages, UserDeposits,
+};
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -132,5 +135,98 @@ thread_local! {
             MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(17))),
         )
     );
+
+    pub static SLASH_RECORDS: RefCell<StableBTreeMap<u64, SlashRecord, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(18))),
+        )
+    );
+
+    pub static ORACLE_PRINCIPAL: RefCell<StableCell<Principal, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(19))),
+            Principal::anonymous()
+        ).expect("Failed to initialize ORACLE_PRINCIPAL")
+    );
+
+    pub static INCIDENT_COUNTER: RefCell<StableCell<u64, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(20))),
+            0u64
+        ).expect("Failed to initialize INCIDENT_COUNTER")
+    );
+
+    pub static INCIDENTS: RefCell<StableBTreeMap<u64, Incident, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(21))),
+        )
+    );
+
+    pub static SUBSCRIPTIONS: RefCell<StableBTreeMap<u64, Subscription, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(22))),
+        )
+    );
+
+    pub static PROTOCOL_TREASURY_PRINCIPAL: RefCell<StableCell<Principal, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(23))),
+            Principal::anonymous()
+        ).expect("Failed to initialize PROTOCOL_TREASURY_PRINCIPAL")
+    );
+
+    pub static PROTOCOL_FEE_BPS: RefCell<StableCell<u64, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(24))),
+            0u64
+        ).expect("Failed to initialize PROTOCOL_FEE_BPS")
+    );
+
+    pub static MANAGER_FEE_BPS: RefCell<StableCell<u64, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(25))),
+            0u64
+        ).expect("Failed to initialize MANAGER_FEE_BPS")
+    );
+
+    pub static PROTOCOL_FEES_ACCRUED: RefCell<StableCell<StorableNat, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(26))),
+            StorableNat(Nat::from(0u64))
+        ).expect("Failed to initialize PROTOCOL_FEES_ACCRUED")
+    );
+
+    pub static MANAGER_FEES_ACCRUED: RefCell<StableCell<StorableNat, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(27))),
+            StorableNat(Nat::from(0u64))
+        ).expect("Failed to initialize MANAGER_FEES_ACCRUED")
+    );
+
+    pub static TOTAL_PREMIUMS_COLLECTED: RefCell<StableCell<StorableNat, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(28))),
+            StorableNat(Nat::from(0u64))
+        ).expect("Failed to initialize TOTAL_PREMIUMS_COLLECTED")
+    );
+
+    pub static REFERRERS: RefCell<StableBTreeMap<Principal, Referrer, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(29))),
+        )
+    );
+
+    pub static COVERED_ACCOUNT_COVERAGES: RefCell<StableBTreeMap<Principal, UserCoverages, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(30))),
+        )
+    );
+
+    pub static PROTOCOL_OWNER_PRINCIPAL: RefCell<StableCell<Principal, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(31))),
+            Principal::anonymous()
+        ).expect("Failed to initialize PROTOCOL_OWNER_PRINCIPAL")
+    );
     
 }
@@ -0,0 +1,204 @@
+use crate::coverage::{apply_coverage_extension, prepare_coverage_extension};
+use crate::episodes::process_episodes;
+use crate::fees::accrue_premium_fees;
+use crate::ledger::{transfer_from_icrc2, transfer_icrc1};
+use crate::referrals::accrue_referral_commission;
+use crate::storage::*;
+use crate::types::{Coverage, CoverageStatus, PoolError, Subscription, SubscriptionStatus};
+use crate::EPISODE_DURATION;
+
+// Subscriptions are charged one episode ahead, so a failed payment leaves the subscriber an
+// episode of grace, while the coverage still runs, before it lapses.
+pub const SUBSCRIPTION_RENEWAL_WINDOW: u64 = 2 * EPISODE_DURATION;
+
+#[ic_cdk::update]
+pub fn subscribe_coverage(coverage_id: u64) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+
+    let coverage = COVERAGES
+        .with(|coverages| coverages.borrow().get(&coverage_id))
+        .ok_or(PoolError::CoverageNotFound)?;
+
+    if coverage.buyer != caller {
+        return Err(PoolError::NotCoverageBuyer);
+    }
+
+    let current_time = ic_cdk::api::time() / 1_000_000_000;
+
+    if coverage.status != CoverageStatus::Active || current_time >= coverage.end_time {
+        return Err(PoolError::CoverageNotActive);
+    }
+
+    if let Some(subscription) = SUBSCRIPTIONS.with(|s| s.borrow().get(&coverage_id)) {
+        if is_renewing(&subscription) {
+            return Err(PoolError::SubscriptionAlreadyActive);
+        }
+    }
+
+    let subscription = Subscription {
+        coverage_id,
+        subscriber: caller,
+        status: SubscriptionStatus::Active,
+        created_at: current_time,
+        last_payment_at: None,
+        past_due_since: None,
+    };
+
+    SUBSCRIPTIONS.with(|s| {
+        s.borrow_mut().insert(coverage_id, subscription);
+    });
+
+    Ok(())
+}
+
+#[ic_cdk::update]
+pub fn cancel_subscription(coverage_id: u64) -> Result<(), PoolError> {
+    let caller = ic_cdk::caller();
+
+    let mut subscription = SUBSCRIPTIONS
+        .with(|s| s.borrow().get(&coverage_id))
+        .ok_or(PoolError::SubscriptionNotFound)?;
+
+    if subscription.subscriber != caller {
+        return Err(PoolError::NotCoverageBuyer);
+    }
+
+    if !is_renewing(&subscription) {
+        return Err(PoolError::SubscriptionNotActive);
+    }
+
+    // The coverage itself keeps running until the end of the last paid period.
+    subscription.status = SubscriptionStatus::Cancelled;
+    SUBSCRIPTIONS.with(|s| {
+        s.borrow_mut().insert(coverage_id, subscription);
+    });
+
+    Ok(())
+}
+
+pub fn end_subscription(coverage_id: u64) {
+    SUBSCRIPTIONS.with(|s| {
+        let mut subscriptions = s.borrow_mut();
+        if let Some(mut subscription) = subscriptions.get(&coverage_id) {
+            if is_renewing(&subscription) {
+                subscription.status = SubscriptionStatus::Cancelled;
+                subscriptions.insert(coverage_id, subscription);
+            }
+        }
+    });
+}
+
+fn is_renewing(subscription: &Subscription) -> bool {
+    subscription.status == SubscriptionStatus::Active
+        || subscription.status == SubscriptionStatus::PastDue
+}
+
+pub async fn process_subscriptions() {
+    let coverage_ids: Vec<u64> = SUBSCRIPTIONS.with(|s| {
+        s.borrow()
+            .iter()
+            .filter(|(_, subscription)| is_renewing(subscription))
+            .map(|(coverage_id, _)| coverage_id)
+            .collect()
+    });
+
+    for coverage_id in coverage_ids {
+        renew_subscription(coverage_id).await;
+    }
+}
+
+async fn renew_subscription(coverage_id: u64) {
+    let coverage = match COVERAGES.with(|coverages| coverages.borrow().get(&coverage_id)) {
+        Some(coverage) => coverage,
+        None => return,
+    };
+
+    let current_time = ic_cdk::api::time() / 1_000_000_000;
+
+    if coverage.status != CoverageStatus::Active {
+        end_subscription(coverage_id);
+        return;
+    }
+
+    if current_time >= coverage.end_time {
+        update_subscription(coverage_id, |subscription| {
+            subscription.status = SubscriptionStatus::Lapsed;
+        });
+        return;
+    }
+
+    if coverage.end_time > current_time + SUBSCRIPTION_RENEWAL_WINDOW {
+        return;
+    }
+
+    let result = pay_subscription_period(coverage).await;
+    let current_time = ic_cdk::api::time() / 1_000_000_000;
+
+    update_subscription(coverage_id, |subscription| match result {
+        Ok(()) => {
+            subscription.status = SubscriptionStatus::Active;
+            subscription.last_payment_at = Some(current_time);
+            subscription.past_due_since = None;
+        }
+        Err(_) => {
+            subscription.status = SubscriptionStatus::PastDue;
+            subscription.past_due_since.get_or_insert(current_time);
+        }
+    });
+}
+
+// Leaves subscriptions that were cancelled in the meantime untouched.
+fn update_subscription(coverage_id: u64, update: impl FnOnce(&mut Subscription)) {
+    SUBSCRIPTIONS.with(|s| {
+        let mut subscriptions = s.borrow_mut();
+        if let Some(mut subscription) = subscriptions.get(&coverage_id) {
+            if is_renewing(&subscription) {
+                update(&mut subscription);
+                subscriptions.insert(coverage_id, subscription);
+            }
+        }
+    });
+}
+
+// Pulls the next episode's premium from the buyer's ICRC-2 allowance and extends the coverage.
+async fn pay_subscription_period(coverage: Coverage) -> Result<(), PoolError> {
+    let premium_amount = prepare_coverage_extension(&coverage, EPISODE_DURATION)?;
+
+    transfer_from_icrc2(coverage.buyer, premium_amount.clone()).await?;
+
+    // The coverage may have been cancelled, or the pool's capacity taken, while the premium
+    // was being pulled.
+    let mut current_coverage = COVERAGES
+        .with(|coverages| coverages.borrow().get(&coverage.coverage_id))
+        .ok_or(PoolError::CoverageNotFound)?;
+
+    process_episodes();
+    let current_time = ic_cdk::api::time() / 1_000_000_000;
+
+    if current_coverage.status != CoverageStatus::Active
+        || current_coverage.end_time != coverage.end_time
+        || current_time >= current_coverage.end_time
+        || prepare_coverage_extension(&current_coverage, EPISODE_DURATION).is_err()
+    {
+        transfer_icrc1(None, coverage.buyer, premium_amount).await?;
+        return Err(PoolError::CoverageNotActive);
+    }
+
+    // The buyer paid the ledger fee on top, so the whole premium is split.
+    let commission =
+        accrue_referral_commission(current_coverage.referrer, &premium_amount, &premium_amount);
+    let reward_amount = accrue_premium_fees(&premium_amount, premium_amount.clone()) - commission;
+    apply_coverage_extension(
+        &mut current_coverage,
+        EPISODE_DURATION,
+        premium_amount,
+        reward_amount,
+    );
+
+    Ok(())
+}
+
+#[ic_cdk::query]
+pub fn get_subscription(coverage_id: u64) -> Option<Subscription> {
+    SUBSCRIPTIONS.with(|s| s.borrow().get(&coverage_id))
+}
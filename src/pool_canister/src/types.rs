@@ -80,6 +80,30 @@ pub struct TransferArg {
     pub created_at_time: Option<u64>,
 }
 
+#[derive(CandidType, Deserialize, Debug)]
+pub struct TransferFromArgs {
+    pub spender_subaccount: Option<Vec<u8>>,
+    pub from: Account,
+    pub to: Account,
+    pub amount: Nat,
+    pub fee: Option<Nat>,
+    pub memo: Option<Vec<u8>>,
+    pub created_at_time: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum TransferFromError {
+    BadFee { expected_fee: Nat },
+    BadBurn { min_burn_amount: Nat },
+    InsufficientFunds { balance: Nat },
+    InsufficientAllowance { allowance: Nat },
+    TooOld,
+    CreatedInFuture { ledger_time: u64 },
+    Duplicate { duplicate_of: Nat },
+    TemporarilyUnavailable,
+    GenericError { error_code: Nat, message: String },
+}
+
 #[derive(CandidType, Deserialize, Debug)]
 pub struct UserDepositInfo {
     pub deposit_id: u64,
@@ -107,6 +131,31 @@ pub enum PoolError {
     NotEnoughAssetsToCover,
     ProductNotFound,
     InvalidProductParameters,
+    SlashInProgress,
+    NotIncidentReporter,
+    IncidentNotFound,
+    IncidentAlreadyResolved,
+    InvalidIncidentTime,
+    ProductFrozen,
+    CoverageNotFound,
+    NotCoverageBuyer,
+    CoverageNotActive,
+    InsufficientAllowance,
+    SubscriptionNotFound,
+    SubscriptionAlreadyActive,
+    SubscriptionNotActive,
+    CoverageAmountTooSmall,
+    PremiumTooLow,
+    InvalidFee,
+    NotFeeRecipient,
+    ReferrerNotFound,
+    ProductRetired,
+    InvalidStateTransition,
+    CoverageAmountTooLarge,
+    AccountCoverageLimitExceeded,
+    SlashRecordNotFound,
+    SlashNotInProgress,
+    CoverageNotRefundable,
 }
 #[derive(Clone, Debug)]
 pub struct UserDeposits(pub Vec<u64>);
@@ -168,7 +217,151 @@ pub struct Product {
     pub max_pool_allocation_percent: u64,
     pub allocation: Nat,
     pub last_allocation_update: u64,
-    pub active: bool,
+    pub state: ProductState,
+    pub metadata: ProductMetadata,
+    pub parametric_trigger: Option<ParametricTrigger>,
+    pub waiting_period: u64,
+    pub claim_grace_period: u64,
+    pub pricing_curve: Option<PricingCurve>,
+    pub duration_discounts: Vec<DurationDiscount>,
+    pub volume_discounts: Vec<VolumeDiscount>,
+    pub min_premium: Nat,
+    pub min_coverage_amount: Nat,
+    pub max_coverage_per_account: Option<Nat>,
+    pub max_coverage_per_purchase: Option<Nat>,
+}
+
+// Products are created as drafts and only sell cover while active. Retired products never sell
+// cover again, but their existing coverages stay claimable.
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum ProductState {
+    Draft,
+    Active,
+    Paused,
+    Retired,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub enum RiskRating {
+    Unrated,
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub struct ProductMetadata {
+    pub description: String,
+    pub category: String,
+    pub covered_protocols: Vec<String>,
+    pub risk_rating: RiskRating,
+    pub terms_uri: String,
+    pub terms_hash: String,
+}
+
+// Annual rates in basis points. Above `kink_utilization` of the product's capacity the rate
+// grows by `slope_above_kink` per 100% of additional utilization.
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize, PartialEq)]
+pub struct PricingCurve {
+    pub base_rate: u64,
+    pub kink_utilization: u64,
+    pub slope_above_kink: u64,
+}
+
+// Discount tables are sorted by increasing threshold; the highest tier reached applies.
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize, PartialEq)]
+pub struct DurationDiscount {
+    pub min_duration: u64,
+    pub discount_bps: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize, PartialEq)]
+pub struct VolumeDiscount {
+    pub min_amount: Nat,
+    pub discount_bps: u64,
+}
+
+// `required_funding` is the balance the purchase subaccount must hold; funding it costs the
+// buyer one more `transfer_fee`. `error` is what a purchase would fail with right now.
+#[derive(CandidType, Deserialize, Debug)]
+pub struct CoverageQuote {
+    pub premium_amount: Nat,
+    pub duration_discount_bps: u64,
+    pub volume_discount_bps: u64,
+    pub transfer_fee: Nat,
+    pub required_funding: Nat,
+    pub max_coverage_amount: Nat,
+    pub error: Option<PoolError>,
+}
+
+// Claims on parametric products are approved by the claim canister's oracles instead of approvers.
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize, PartialEq)]
+pub struct ParametricTrigger {
+    pub trigger_id: String,
+    pub description: String,
+}
+
+// Products stored by earlier versions lack the fields added since; missing ones decode as None.
+#[derive(CandidType, Deserialize)]
+struct StoredProduct {
+    name: String,
+    product_id: u64,
+    annual_percent: u64,
+    max_coverage_duration: u64,
+    max_pool_allocation_percent: u64,
+    allocation: Nat,
+    last_allocation_update: u64,
+    active: Option<bool>,
+    state: Option<ProductState>,
+    metadata: Option<ProductMetadata>,
+    parametric_trigger: Option<ParametricTrigger>,
+    waiting_period: Option<u64>,
+    claim_grace_period: Option<u64>,
+    pricing_curve: Option<PricingCurve>,
+    duration_discounts: Option<Vec<DurationDiscount>>,
+    volume_discounts: Option<Vec<VolumeDiscount>>,
+    min_premium: Option<Nat>,
+    min_coverage_amount: Option<Nat>,
+    max_coverage_per_account: Option<Nat>,
+    max_coverage_per_purchase: Option<Nat>,
+}
+
+impl From<StoredProduct> for Product {
+    fn from(stored: StoredProduct) -> Self {
+        let state = stored.state.unwrap_or(match stored.active {
+            Some(true) => ProductState::Active,
+            _ => ProductState::Paused,
+        });
+
+        Product {
+            name: stored.name,
+            product_id: stored.product_id,
+            annual_percent: stored.annual_percent,
+            max_coverage_duration: stored.max_coverage_duration,
+            max_pool_allocation_percent: stored.max_pool_allocation_percent,
+            allocation: stored.allocation,
+            last_allocation_update: stored.last_allocation_update,
+            state,
+            metadata: stored.metadata.unwrap_or(ProductMetadata {
+                description: String::new(),
+                category: String::new(),
+                covered_protocols: vec![],
+                risk_rating: RiskRating::Unrated,
+                terms_uri: String::new(),
+                terms_hash: String::new(),
+            }),
+            parametric_trigger: stored.parametric_trigger,
+            waiting_period: stored.waiting_period.unwrap_or(0),
+            claim_grace_period: stored.claim_grace_period.unwrap_or(0),
+            pricing_curve: stored.pricing_curve,
+            duration_discounts: stored.duration_discounts.unwrap_or_default(),
+            volume_discounts: stored.volume_discounts.unwrap_or_default(),
+            min_premium: stored.min_premium.unwrap_or_default(),
+            min_coverage_amount: stored.min_coverage_amount.unwrap_or_default(),
+            max_coverage_per_account: stored.max_coverage_per_account,
+            max_coverage_per_purchase: stored.max_coverage_per_purchase,
+        }
+    }
 }
 
 impl Storable for Product {
@@ -177,7 +370,8 @@ impl Storable for Product {
     }
 
     fn from_bytes(bytes: Cow<[u8]>) -> Self {
-        candid::decode_one(&bytes).unwrap()
+        candid::decode_one(&bytes)
+            .unwrap_or_else(|_| candid::decode_one::<StoredProduct>(&bytes).unwrap().into())
     }
 
     const BOUND: Bound = Bound::Unbounded;
@@ -191,8 +385,58 @@ pub struct Coverage {
     pub product_id: u64,
     pub coverage_amount: Nat,
     pub premium_amount: Nat,
+    pub purchased_at: u64,
     pub start_time: u64,
     pub end_time: u64,
+    pub claim_deadline: u64,
+    pub status: CoverageStatus,
+    pub reward_rate: Nat,
+    pub referrer: Option<Principal>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub enum CoverageStatus {
+    Active,
+    Cancelled,
+}
+
+// Coverages bought before timing, cancellation and referrals were added start at purchase and
+// can be claimed until they end.
+#[derive(CandidType, Deserialize)]
+struct StoredCoverage {
+    coverage_id: u64,
+    buyer: Principal,
+    covered_account: Principal,
+    product_id: u64,
+    coverage_amount: Nat,
+    premium_amount: Nat,
+    purchased_at: Option<u64>,
+    start_time: u64,
+    end_time: u64,
+    claim_deadline: Option<u64>,
+    status: Option<CoverageStatus>,
+    reward_rate: Option<Nat>,
+    referrer: Option<Principal>,
+}
+
+impl From<StoredCoverage> for Coverage {
+    fn from(stored: StoredCoverage) -> Self {
+        Coverage {
+            coverage_id: stored.coverage_id,
+            buyer: stored.buyer,
+            covered_account: stored.covered_account,
+            product_id: stored.product_id,
+            coverage_amount: stored.coverage_amount,
+            premium_amount: stored.premium_amount,
+            purchased_at: stored.purchased_at.unwrap_or(stored.start_time),
+            start_time: stored.start_time,
+            end_time: stored.end_time,
+            claim_deadline: stored.claim_deadline.unwrap_or(stored.end_time),
+            status: stored.status.unwrap_or(CoverageStatus::Active),
+            reward_rate: stored.reward_rate.unwrap_or_default(),
+            referrer: stored.referrer,
+        }
+    }
 }
 
 impl Storable for Coverage {
@@ -201,7 +445,8 @@ impl Storable for Coverage {
     }
 
     fn from_bytes(bytes: Cow<[u8]>) -> Self {
-        candid::decode_one(&bytes).unwrap()
+        candid::decode_one(&bytes)
+            .unwrap_or_else(|_| candid::decode_one::<StoredCoverage>(&bytes).unwrap().into())
     }
 
     const BOUND: Bound = Bound::Unbounded;
@@ -221,3 +466,139 @@ impl Storable for UserCoverages {
 
     const BOUND: Bound = Bound::Unbounded;
 }
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub enum SlashStatus {
+    InProgress,
+    Completed,
+    TransferFailed,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub struct SlashRecord {
+    pub receiver: Principal,
+    pub amount: Nat,
+    pub slashed_amount: Nat,
+    pub status: SlashStatus,
+    pub created_at: u64,
+    pub attempted_at: u64,
+}
+
+impl Storable for SlashRecord {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub enum IncidentSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub enum IncidentStatus {
+    Open,
+    Resolved,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub struct Incident {
+    pub incident_id: u64,
+    pub product_id: u64,
+    pub incident_time: u64,
+    pub severity: IncidentSeverity,
+    pub status: IncidentStatus,
+    pub reported_by: Principal,
+    pub reported_at: u64,
+    pub resolved_at: Option<u64>,
+}
+
+impl Storable for Incident {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub enum SubscriptionStatus {
+    Active,
+    PastDue,
+    Lapsed,
+    Cancelled,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub struct Subscription {
+    pub coverage_id: u64,
+    pub subscriber: Principal,
+    pub status: SubscriptionStatus,
+    pub created_at: u64,
+    pub last_payment_at: Option<u64>,
+    pub past_due_since: Option<u64>,
+}
+
+impl Storable for Subscription {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct FeeConfig {
+    pub protocol_treasury: Principal,
+    pub protocol_fee_bps: u64,
+    pub manager_fee_bps: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PoolAnalytics {
+    pub total_assets: Nat,
+    pub total_shares: Nat,
+    pub total_cover_allocation: Nat,
+    pub pool_reward_rate: Nat,
+    pub total_premiums_collected: Nat,
+    pub protocol_fees_accrued: Nat,
+    pub manager_fees_accrued: Nat,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub struct Referrer {
+    pub referrer: Principal,
+    pub commission_bps: u64,
+    pub commissions_accrued: Nat,
+    pub commissions_earned: Nat,
+    pub premium_volume: Nat,
+}
+
+impl Storable for Referrer {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
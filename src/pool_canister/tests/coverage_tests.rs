@@ -4,6 +4,10 @@ use commons::{
     get_stakable_episode_with_client, purchase_coverage, transfer_to_subaccount,
     LedgerCanisterClient, PoolCanisterClient, TRANSFER_FEE,
 };
+use pool_canister::{
+    DurationDiscount, ParametricTrigger, PricingCurve, ProductMetadata, ProductState, RiskRating,
+    VolumeDiscount,
+};
 
 mod setup;
 use setup::setup;
@@ -45,9 +49,13 @@ fn test_create_product_and_purchase_coverage() {
 
     assert!(product_id.is_ok(), "Product creation should succeed");
     let product_id = product_id.unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
 
     // Verify product was created
-    let all_products = pool_client.get_products();
+    let all_products = pool_client.get_products(None);
     assert!(!all_products.is_empty(), "Should have at least one product");
 
     let product = all_products
@@ -57,7 +65,7 @@ fn test_create_product_and_purchase_coverage() {
 
     assert_eq!(product.annual_percent, annual_percent);
     assert_eq!(product.max_coverage_duration, max_coverage_duration);
-    assert_eq!(product.active, true);
+    assert_eq!(product.state, ProductState::Active);
 
     // Purchase coverage
     let covered_account = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
@@ -87,7 +95,7 @@ fn test_create_product_and_purchase_coverage() {
     );
 
     // Verify product allocation increased
-    let all_products_after = pool_client.get_products();
+    let all_products_after = pool_client.get_products(None);
     let product_after = all_products_after
         .iter()
         .find(|p| p.product_id == product_id)
@@ -174,6 +182,10 @@ fn test_coverage_allocation_limits() {
             5000u64,
         )
         .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
 
     // Try to purchase coverage exceeding allocation limit
     let covered_account = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
@@ -233,6 +245,10 @@ fn test_independent_product_allocations() {
             5000u64,
         )
         .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product1_id, ProductState::Active)
+        .unwrap();
 
     let product2_id = pool_client
         .connect(pool_manager)
@@ -243,6 +259,10 @@ fn test_independent_product_allocations() {
             10000u64,
         )
         .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product2_id, ProductState::Active)
+        .unwrap();
 
     let covered_account = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
     let coverage_duration = pool_canister::EPISODE_DURATION * 3;
@@ -328,6 +348,10 @@ fn test_excess_balance_refund() {
             5000u64,
         )
         .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
 
     // Get buyer's initial balance
     let buyer_initial_balance = ledger_client.icrc1_balance_of(pool_canister::Account {
@@ -366,6 +390,7 @@ fn test_excess_balance_refund() {
         covered_account,
         coverage_duration,
         coverage_amount.clone(),
+        None,
     );
 
     assert!(
@@ -440,6 +465,10 @@ fn test_coverage_allocation_decreases_on_expiry() {
             5000u64,
         )
         .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
 
     // Purchase coverage
     let covered_account = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
@@ -466,7 +495,7 @@ fn test_coverage_allocation_decreases_on_expiry() {
     );
 
     // Verify product allocation increased
-    let all_products_before = pool_client.get_products();
+    let all_products_before = pool_client.get_products(None);
     let product_before = all_products_before
         .iter()
         .find(|p| p.product_id == product_id)
@@ -495,7 +524,7 @@ fn test_coverage_allocation_decreases_on_expiry() {
     advance_time(&pic, time_to_advance);
 
     // Verify product allocation decreased to 0
-    let all_products_after = pool_client.get_products();
+    let all_products_after = pool_client.get_products(None);
     let product_after = all_products_after
         .iter()
         .find(|p| p.product_id == product_id)
@@ -549,6 +578,10 @@ fn test_insufficient_balance_refund() {
             5000u64,
         )
         .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
 
     // Get buyer's initial balance
     let buyer_initial_balance = ledger_client.icrc1_balance_of(pool_canister::Account {
@@ -586,6 +619,7 @@ fn test_insufficient_balance_refund() {
         covered_account,
         coverage_duration,
         coverage_amount.clone(),
+        None,
     );
 
     assert!(
@@ -618,7 +652,7 @@ fn test_insufficient_balance_refund() {
     );
 
     // Verify product allocation did not increase
-    let all_products = pool_client.get_products();
+    let all_products = pool_client.get_products(None);
     let product = all_products
         .iter()
         .find(|p| p.product_id == product_id)
@@ -717,7 +751,7 @@ fn test_invalid_product_creation_parameters() {
 
     // Verify the product was created with correct parameters
     let product_id = result.unwrap();
-    let all_products = pool_client.get_products();
+    let all_products = pool_client.get_products(None);
     let product = all_products
         .iter()
         .find(|p| p.product_id == product_id)
@@ -729,5 +763,1436 @@ fn test_invalid_product_creation_parameters() {
         pool_canister::EPISODE_DURATION * 6
     );
     assert_eq!(product.max_pool_allocation_percent, 5000u64);
-    assert_eq!(product.active, true);
+    assert_eq!(product.state, ProductState::Draft);
+}
+
+#[test]
+fn test_set_product_parametric_trigger() {
+    let (pic, pool_canister, _ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+    let stranger = Principal::from_slice(&[9u8; 29]);
+
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Stablecoin depeg".to_string(),
+            500u64,
+            pool_canister::EPISODE_DURATION * 6,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let product = pool_client.get_product(product_id).unwrap();
+    assert_eq!(product.parametric_trigger, None);
+
+    let trigger = ParametricTrigger {
+        trigger_id: "usdc-depeg".to_string(),
+        description: "USDC trades below 0.95 for an hour".to_string(),
+    };
+
+    let result = pool_client
+        .connect(stranger)
+        .set_product_parametric_trigger(product_id, Some(trigger.clone()));
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::NotPoolManager)
+    ));
+
+    let result = pool_client
+        .connect(pool_manager)
+        .set_product_parametric_trigger(
+            product_id,
+            Some(ParametricTrigger {
+                trigger_id: " ".to_string(),
+                description: String::new(),
+            }),
+        );
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::InvalidProductParameters)
+    ));
+
+    let result = pool_client
+        .connect(pool_manager)
+        .set_product_parametric_trigger(product_id, Some(trigger.clone()));
+    assert!(result.is_ok());
+    assert_eq!(
+        pool_client
+            .get_product(product_id)
+            .unwrap()
+            .parametric_trigger,
+        Some(trigger)
+    );
+
+    let result = pool_client
+        .connect(pool_manager)
+        .set_product_parametric_trigger(product_id, None);
+    assert!(result.is_ok());
+    assert_eq!(
+        pool_client
+            .get_product(product_id)
+            .unwrap()
+            .parametric_trigger,
+        None
+    );
+
+    let result = pool_client
+        .connect(pool_manager)
+        .set_product_parametric_trigger(999, None);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::ProductNotFound)
+    ));
+}
+
+#[test]
+fn test_waiting_period_delays_coverage_start() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user1 = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user1,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let max_coverage_duration = pool_canister::EPISODE_DURATION * 2;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            max_coverage_duration,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let waiting_period = 24 * 60 * 60;
+
+    let result = pool_client
+        .connect(buyer)
+        .set_product_waiting_period(product_id, waiting_period);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::NotPoolManager)
+    ));
+
+    // The waiting period and the longest cover must fit within the active episodes
+    let result = pool_client
+        .connect(pool_manager)
+        .set_product_waiting_period(product_id, pool_canister::EPISODE_DURATION * 21);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::InvalidProductParameters)
+    ));
+
+    pool_client
+        .connect(pool_manager)
+        .set_product_waiting_period(product_id, waiting_period)
+        .expect("Pool manager should set the waiting period");
+    assert_eq!(
+        pool_client.get_product(product_id).unwrap().waiting_period,
+        waiting_period
+    );
+
+    // The premium only covers the effective period after the waiting period
+    let coverage_duration = pool_canister::EPISODE_DURATION;
+    let coverage_amount = Nat::from(100_000_000u64);
+    let premium_amount =
+        calculate_premium(coverage_duration, annual_percent, coverage_amount.clone());
+    let purchase_time = get_current_time(&pic);
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        coverage_amount,
+        premium_amount.clone(),
+    )
+    .expect("Coverage purchase should succeed");
+
+    let coverage = &pool_client.get_coverages(buyer)[0];
+    assert!(coverage.purchased_at >= purchase_time);
+    assert_eq!(coverage.start_time, coverage.purchased_at + waiting_period);
+    assert_eq!(coverage.end_time, coverage.start_time + coverage_duration);
+    assert_eq!(coverage.premium_amount, premium_amount);
+}
+
+#[test]
+fn test_claim_grace_period_keeps_capital_locked() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user1 = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user1,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            pool_canister::EPISODE_DURATION * 2,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let claim_grace_period = pool_canister::EPISODE_DURATION;
+
+    let result = pool_client
+        .connect(buyer)
+        .set_product_claim_grace_period(product_id, claim_grace_period);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::NotPoolManager)
+    ));
+
+    // The grace period and the longest cover must fit within the active episodes
+    let result = pool_client
+        .connect(pool_manager)
+        .set_product_claim_grace_period(product_id, pool_canister::EPISODE_DURATION * 21);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::InvalidProductParameters)
+    ));
+
+    pool_client
+        .connect(pool_manager)
+        .set_product_claim_grace_period(product_id, claim_grace_period)
+        .expect("Pool manager should set the claim grace period");
+
+    let coverage_duration = pool_canister::EPISODE_DURATION;
+    let coverage_amount = Nat::from(100_000_000u64);
+    let premium_amount =
+        calculate_premium(coverage_duration, annual_percent, coverage_amount.clone());
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        coverage_amount.clone(),
+        premium_amount,
+    )
+    .expect("Coverage purchase should succeed");
+
+    let coverage = &pool_client.get_coverages(buyer)[0];
+    assert_eq!(
+        coverage.claim_deadline,
+        coverage.end_time + claim_grace_period
+    );
+
+    // The allocation is only released in the episode of the claim deadline
+    let deadline_episode = coverage.claim_deadline / pool_canister::EPISODE_DURATION;
+    let end_episode = coverage.end_time / pool_canister::EPISODE_DURATION;
+    assert_ne!(deadline_episode, end_episode);
+    assert_eq!(
+        pool_client
+            .get_episode(deadline_episode)
+            .unwrap()
+            .coverage_decrease,
+        coverage_amount
+    );
+
+    advance_time(&pic, coverage.end_time - get_current_time(&pic) + 1);
+    pool_client.update_episodes_state();
+    assert_eq!(
+        pool_client.get_product(product_id).unwrap().allocation,
+        coverage_amount
+    );
+}
+
+#[test]
+fn test_cancel_coverage_refunds_unused_premium() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user1 = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user1,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            pool_canister::EPISODE_DURATION * 6,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let coverage_duration = pool_canister::EPISODE_DURATION * 2;
+    let coverage_amount = Nat::from(100_000_000u64);
+    let premium_amount =
+        calculate_premium(coverage_duration, annual_percent, coverage_amount.clone());
+    let reward_rate_before = pool_client.get_pool_reward_rate();
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        coverage_amount,
+        premium_amount.clone(),
+    )
+    .expect("Coverage purchase should succeed");
+
+    let coverage = pool_client.get_coverages(buyer)[0].clone();
+    assert_eq!(coverage.status, pool_canister::CoverageStatus::Active);
+
+    let result = pool_client
+        .connect(user1)
+        .cancel_coverage(coverage.coverage_id);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::NotCoverageBuyer)
+    ));
+
+    advance_time(&pic, pool_canister::EPISODE_DURATION);
+
+    let buyer_account = pool_canister::Account {
+        owner: buyer,
+        subaccount: None,
+    };
+    let balance_before = ledger_client.icrc1_balance_of(buyer_account.clone());
+
+    let refund_amount = pool_client
+        .connect(buyer)
+        .cancel_coverage(coverage.coverage_id)
+        .expect("Buyer should cancel the coverage");
+
+    // Only the part of the premium not yet streamed is refunded, minus the cancellation fee
+    assert!(refund_amount > Nat::from(0u64));
+    assert!(refund_amount < premium_amount);
+    assert_eq!(
+        ledger_client.icrc1_balance_of(buyer_account),
+        balance_before + refund_amount - TRANSFER_FEE.clone()
+    );
+
+    // Only the cancellation fee keeps streaming to LPs
+    let refunded_rate = coverage.reward_rate.clone() * Nat::from(9_500u64) / Nat::from(10_000u64);
+    assert_eq!(
+        pool_client.get_pool_reward_rate(),
+        reward_rate_before + coverage.reward_rate.clone() - refunded_rate
+    );
+
+    // The allocation is released immediately
+    assert_eq!(
+        pool_client.get_product(product_id).unwrap().allocation,
+        Nat::from(0u64)
+    );
+    assert_eq!(pool_client.get_total_cover_allocation(), Nat::from(0u64));
+
+    let cancelled = pool_client.get_coverage(coverage.coverage_id).unwrap();
+    assert_eq!(cancelled.status, pool_canister::CoverageStatus::Cancelled);
+    assert!(cancelled.end_time < coverage.end_time);
+
+    let result = pool_client
+        .connect(buyer)
+        .cancel_coverage(coverage.coverage_id);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::CoverageNotActive)
+    ));
+}
+
+#[test]
+fn test_extend_coverage_keeps_coverage_id() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user1 = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 5);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user1,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            pool_canister::EPISODE_DURATION * 3,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let coverage_duration = pool_canister::EPISODE_DURATION;
+    let coverage_amount = Nat::from(100_000_000u64);
+    let premium_amount =
+        calculate_premium(coverage_duration, annual_percent, coverage_amount.clone());
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        coverage_amount.clone(),
+        premium_amount.clone(),
+    )
+    .expect("Coverage purchase should succeed");
+
+    let coverage = pool_client.get_coverages(buyer)[0].clone();
+
+    let result = pool_client
+        .connect(user1)
+        .extend_coverage(coverage.coverage_id, coverage_duration);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::NotCoverageBuyer)
+    ));
+
+    let result = pool_client
+        .connect(buyer)
+        .extend_coverage(coverage.coverage_id, coverage_duration * 3);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::CoverageDurationTooLong)
+    ));
+
+    // The extension is paid at the product's current rate from the purchase subaccount
+    let extension_premium =
+        calculate_premium(coverage_duration, annual_percent, coverage_amount.clone());
+    let purchase_subaccount = pool_client
+        .connect(buyer)
+        .get_purchase_subaccount(buyer, product_id);
+    transfer_to_subaccount(
+        &mut ledger_client,
+        buyer,
+        pool_canister,
+        purchase_subaccount,
+        extension_premium.clone(),
+    );
+
+    pool_client
+        .connect(buyer)
+        .extend_coverage(coverage.coverage_id, coverage_duration)
+        .expect("Buyer should extend the coverage");
+
+    let coverages = pool_client.get_coverages(buyer);
+    assert_eq!(coverages.len(), 1);
+    let extended = &coverages[0];
+    assert_eq!(extended.coverage_id, coverage.coverage_id);
+    assert_eq!(extended.start_time, coverage.start_time);
+    assert_eq!(extended.end_time, coverage.end_time + coverage_duration);
+    assert_eq!(extended.premium_amount, premium_amount + extension_premium);
+
+    // The allocation is now released in the new expiry episode
+    let old_episode = coverage.claim_deadline / pool_canister::EPISODE_DURATION;
+    let new_episode = extended.claim_deadline / pool_canister::EPISODE_DURATION;
+    assert_eq!(
+        pool_client
+            .get_episode(old_episode)
+            .unwrap()
+            .coverage_decrease,
+        Nat::from(0u64)
+    );
+    assert_eq!(
+        pool_client
+            .get_episode(new_episode)
+            .unwrap()
+            .coverage_decrease,
+        coverage_amount
+    );
+
+    advance_time(&pic, coverage.end_time - get_current_time(&pic) + 1);
+    pool_client.update_episodes_state();
+    assert_eq!(
+        pool_client.get_product(product_id).unwrap().allocation,
+        coverage_amount
+    );
+}
+
+#[test]
+fn test_quote_coverage_previews_premium_and_capacity() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user1 = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user1,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            pool_canister::EPISODE_DURATION * 3,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let coverage_duration = pool_canister::EPISODE_DURATION;
+    let coverage_amount = Nat::from(100_000_000u64);
+    let premium_amount =
+        calculate_premium(coverage_duration, annual_percent, coverage_amount.clone());
+
+    let quote = pool_client
+        .quote_coverage(product_id, coverage_duration, coverage_amount.clone(), None)
+        .unwrap();
+    assert_eq!(quote.premium_amount, premium_amount);
+    assert_eq!(quote.transfer_fee, TRANSFER_FEE.clone());
+    assert_eq!(quote.required_funding, premium_amount);
+    // Half of the deposited assets may back this product
+    let max_coverage_amount = (Nat::from(1_000_000_000u64) - TRANSFER_FEE.clone()) / 2u64;
+    assert_eq!(quote.max_coverage_amount, max_coverage_amount);
+    assert!(quote.error.is_none());
+
+    // The quote reports the error the purchase would fail with
+    let quote = pool_client
+        .quote_coverage(
+            product_id,
+            coverage_duration,
+            max_coverage_amount.clone() + 1u64,
+            None,
+        )
+        .unwrap();
+    assert!(matches!(
+        quote.error,
+        Some(pool_canister::PoolError::NotEnoughAssetsToCover)
+    ));
+
+    let quote = pool_client
+        .quote_coverage(
+            product_id,
+            pool_canister::EPISODE_DURATION * 4,
+            coverage_amount.clone(),
+            None,
+        )
+        .unwrap();
+    assert!(matches!(
+        quote.error,
+        Some(pool_canister::PoolError::CoverageDurationTooLong)
+    ));
+
+    let result = pool_client.quote_coverage(product_id, u64::MAX, coverage_amount.clone(), None);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::CoverageDurationTooLong)
+    ));
+
+    let result = pool_client.quote_coverage(
+        product_id + 1,
+        coverage_duration,
+        coverage_amount.clone(),
+        None,
+    );
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::ProductNotFound)
+    ));
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        coverage_amount.clone(),
+        premium_amount,
+    )
+    .expect("Coverage purchase should succeed");
+
+    let quote = pool_client
+        .quote_coverage(product_id, coverage_duration, coverage_amount.clone(), None)
+        .unwrap();
+    assert_eq!(
+        quote.max_coverage_amount,
+        max_coverage_amount - coverage_amount
+    );
+}
+
+#[test]
+fn test_pricing_curve_raises_premium_with_utilization() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user1 = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user1,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let max_coverage_duration = pool_canister::EPISODE_DURATION * 3;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            max_coverage_duration,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let curve = PricingCurve {
+        base_rate: annual_percent,
+        kink_utilization: 5000,
+        slope_above_kink: 2000,
+    };
+
+    let result = pool_client.connect(user1).set_product(
+        product_id,
+        annual_percent,
+        max_coverage_duration,
+        5000u64,
+        Some(curve.clone()),
+    );
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::NotPoolManager)
+    ));
+
+    let result = pool_client.connect(pool_manager).set_product(
+        product_id,
+        annual_percent,
+        max_coverage_duration,
+        5000u64,
+        Some(PricingCurve {
+            kink_utilization: 10_001,
+            ..curve.clone()
+        }),
+    );
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::InvalidProductParameters)
+    ));
+
+    pool_client
+        .connect(pool_manager)
+        .set_product(
+            product_id,
+            annual_percent,
+            max_coverage_duration,
+            5000u64,
+            Some(curve.clone()),
+        )
+        .unwrap();
+    assert_eq!(
+        pool_client.get_product(product_id).unwrap().pricing_curve,
+        Some(curve)
+    );
+
+    let coverage_duration = pool_canister::EPISODE_DURATION;
+    let small_amount = Nat::from(100_000_000u64);
+    let large_amount = Nat::from(400_000_000u64);
+
+    // Below the kink the curve charges its base rate
+    let small_quote = pool_client
+        .quote_coverage(product_id, coverage_duration, small_amount.clone(), None)
+        .unwrap();
+    assert_eq!(
+        small_quote.premium_amount,
+        calculate_premium(coverage_duration, annual_percent, small_amount.clone())
+    );
+
+    // Above it the rate climbs, up to base plus slope at full utilization
+    let large_quote = pool_client
+        .quote_coverage(product_id, coverage_duration, large_amount.clone(), None)
+        .unwrap();
+    assert!(
+        large_quote.premium_amount
+            > calculate_premium(coverage_duration, annual_percent, large_amount.clone())
+    );
+    assert!(
+        large_quote.premium_amount
+            < calculate_premium(
+                coverage_duration,
+                annual_percent + 2000,
+                large_amount.clone()
+            )
+    );
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        large_amount.clone(),
+        large_quote.premium_amount.clone(),
+    )
+    .expect("Coverage purchase should succeed at the quoted premium");
+
+    let coverage = pool_client.get_coverages(buyer)[0].clone();
+    assert_eq!(coverage.premium_amount, large_quote.premium_amount);
+
+    // The same cover costs more once it sits higher on the curve
+    let quote = pool_client
+        .quote_coverage(product_id, coverage_duration, small_amount.clone(), None)
+        .unwrap();
+    assert!(quote.premium_amount > small_quote.premium_amount);
+}
+
+#[test]
+fn test_duration_and_volume_discounts_reduce_premium() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user1 = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user1,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            pool_canister::EPISODE_DURATION * 3,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let duration_discounts = vec![DurationDiscount {
+        min_duration: pool_canister::EPISODE_DURATION * 2,
+        discount_bps: 1000,
+    }];
+    let volume_discounts = vec![
+        VolumeDiscount {
+            min_amount: Nat::from(50_000_000u64),
+            discount_bps: 1000,
+        },
+        VolumeDiscount {
+            min_amount: Nat::from(200_000_000u64),
+            discount_bps: 2000,
+        },
+    ];
+
+    let result = pool_client.connect(user1).set_product_discounts(
+        product_id,
+        duration_discounts.clone(),
+        volume_discounts.clone(),
+    );
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::NotPoolManager)
+    ));
+
+    // Tiers must be sorted by threshold and discount less than the whole premium
+    let mut unsorted = volume_discounts.clone();
+    unsorted.reverse();
+    let result = pool_client.connect(pool_manager).set_product_discounts(
+        product_id,
+        duration_discounts.clone(),
+        unsorted,
+    );
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::InvalidProductParameters)
+    ));
+
+    let result = pool_client.connect(pool_manager).set_product_discounts(
+        product_id,
+        vec![DurationDiscount {
+            min_duration: pool_canister::EPISODE_DURATION,
+            discount_bps: 10_000,
+        }],
+        vec![],
+    );
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::InvalidProductParameters)
+    ));
+
+    pool_client
+        .connect(pool_manager)
+        .set_product_discounts(product_id, duration_discounts, volume_discounts)
+        .unwrap();
+
+    let small_amount = Nat::from(10_000_000u64);
+    let quote = pool_client
+        .quote_coverage(
+            product_id,
+            pool_canister::EPISODE_DURATION,
+            small_amount.clone(),
+            None,
+        )
+        .unwrap();
+    assert_eq!(quote.duration_discount_bps, 0);
+    assert_eq!(quote.volume_discount_bps, 0);
+    assert_eq!(
+        quote.premium_amount,
+        calculate_premium(
+            pool_canister::EPISODE_DURATION,
+            annual_percent,
+            small_amount
+        )
+    );
+
+    // 10% off for the term and 20% off for the volume compound to 360 bps a year
+    let coverage_duration = pool_canister::EPISODE_DURATION * 2;
+    let coverage_amount = Nat::from(200_000_000u64);
+    let quote = pool_client
+        .quote_coverage(product_id, coverage_duration, coverage_amount.clone(), None)
+        .unwrap();
+    assert_eq!(quote.duration_discount_bps, 1000);
+    assert_eq!(quote.volume_discount_bps, 2000);
+
+    let discounted_premium = calculate_premium(coverage_duration, 360, coverage_amount.clone());
+    assert_eq!(quote.premium_amount, discounted_premium);
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        coverage_amount,
+        discounted_premium.clone(),
+    )
+    .expect("Coverage purchase should succeed at the discounted premium");
+
+    let coverage = pool_client.get_coverages(buyer)[0].clone();
+    assert_eq!(coverage.premium_amount, discounted_premium);
+}
+
+#[test]
+fn test_minimum_premium_and_cover_amount() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user1 = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user1,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            pool_canister::EPISODE_DURATION * 3,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let coverage_duration = pool_canister::EPISODE_DURATION;
+    let dust_amount = Nat::from(100u64);
+
+    // Fractions of a token are charged in full rather than rounded away
+    let dust_premium = calculate_premium(coverage_duration, annual_percent, dust_amount.clone());
+    assert_eq!(dust_premium, Nat::from(1u64));
+
+    let quote = pool_client
+        .quote_coverage(product_id, coverage_duration, dust_amount.clone(), None)
+        .unwrap();
+    assert_eq!(quote.premium_amount, dust_premium);
+    assert!(matches!(
+        quote.error,
+        Some(pool_canister::PoolError::PremiumTooLow)
+    ));
+
+    // A premium the ledger fee would swallow is rejected before anything is allocated
+    let result = purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        dust_amount.clone(),
+        TRANSFER_FEE.clone(),
+    );
+    assert!(result.unwrap_err().contains("PremiumTooLow"));
+    assert_eq!(pool_client.get_total_cover_allocation(), Nat::from(0u64));
+    assert_eq!(
+        pool_client.get_product(product_id).unwrap().allocation,
+        Nat::from(0u64)
+    );
+    assert!(pool_client.get_coverages(buyer).is_empty());
+
+    let min_premium = Nat::from(1_000u64);
+    let min_coverage_amount = Nat::from(1_000u64);
+
+    let result = pool_client.connect(user1).set_product_minimums(
+        product_id,
+        min_premium.clone(),
+        min_coverage_amount.clone(),
+    );
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::NotPoolManager)
+    ));
+
+    pool_client
+        .connect(pool_manager)
+        .set_product_minimums(product_id, min_premium.clone(), min_coverage_amount.clone())
+        .unwrap();
+
+    let quote = pool_client
+        .quote_coverage(product_id, coverage_duration, dust_amount.clone(), None)
+        .unwrap();
+    assert!(matches!(
+        quote.error,
+        Some(pool_canister::PoolError::CoverageAmountTooSmall)
+    ));
+
+    let result = purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        dust_amount,
+        min_premium.clone(),
+    );
+    assert!(result.unwrap_err().contains("CoverageAmountTooSmall"));
+
+    // Small covers are charged the product's minimum premium
+    let quote = pool_client
+        .quote_coverage(
+            product_id,
+            coverage_duration,
+            min_coverage_amount.clone(),
+            None,
+        )
+        .unwrap();
+    assert_eq!(quote.premium_amount, min_premium);
+    assert!(quote.error.is_none());
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        min_coverage_amount,
+        min_premium.clone(),
+    )
+    .expect("Coverage purchase should succeed at the minimum premium");
+
+    let coverage = pool_client.get_coverages(buyer)[0].clone();
+    assert_eq!(coverage.premium_amount, min_premium);
+}
+
+#[test]
+fn test_product_lifecycle_and_metadata() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user1 = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user1,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            pool_canister::EPISODE_DURATION * 3,
+            5000u64,
+        )
+        .unwrap();
+
+    let coverage_duration = pool_canister::EPISODE_DURATION;
+    let coverage_amount = Nat::from(100_000_000u64);
+    let premium_amount =
+        calculate_premium(coverage_duration, annual_percent, coverage_amount.clone());
+
+    // New products start as drafts and can't be sold
+    let product = pool_client.get_product(product_id).unwrap();
+    assert_eq!(product.state, ProductState::Draft);
+    assert_eq!(product.metadata.risk_rating, RiskRating::Unrated);
+
+    let result = purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        coverage_amount.clone(),
+        premium_amount.clone(),
+    );
+    assert!(result.unwrap_err().contains("ProductNotActive"));
+
+    let metadata = ProductMetadata {
+        description: "Covers funds lost to a bridge exploit".to_string(),
+        category: "Bridge".to_string(),
+        covered_protocols: vec!["ckBTC minter".to_string()],
+        risk_rating: RiskRating::Medium,
+        terms_uri: "https://example.com/terms/bridge".to_string(),
+        terms_hash: "9f86d081884c7d65".to_string(),
+    };
+
+    let result = pool_client
+        .connect(buyer)
+        .set_product_metadata(product_id, metadata.clone());
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::NotPoolManager)
+    ));
+
+    let result = pool_client
+        .connect(buyer)
+        .set_product_state(product_id, ProductState::Active);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::NotPoolManager)
+    ));
+
+    pool_client
+        .connect(pool_manager)
+        .set_product_metadata(product_id, metadata.clone())
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let product = pool_client.get_product(product_id).unwrap();
+    assert_eq!(product.state, ProductState::Active);
+    assert_eq!(product.metadata, metadata);
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        coverage_amount.clone(),
+        premium_amount.clone(),
+    )
+    .expect("Coverage purchase should succeed once the product is active");
+
+    // Paused products stop selling until they are reactivated
+    let result = pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Draft);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::InvalidStateTransition)
+    ));
+
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Paused)
+        .unwrap();
+
+    let result = purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        coverage_amount.clone(),
+        premium_amount.clone(),
+    );
+    assert!(result.unwrap_err().contains("ProductNotActive"));
+
+    let paused_products = pool_client.get_products(Some(ProductState::Paused));
+    assert_eq!(paused_products.len(), 1);
+    assert_eq!(paused_products[0].product_id, product_id);
+    assert!(pool_client
+        .get_products(Some(ProductState::Active))
+        .is_empty());
+
+    // Retired products are hidden by default but their coverages stay in force
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Retired)
+        .unwrap();
+
+    assert!(pool_client.get_products(None).is_empty());
+    let retired_products = pool_client.get_products(Some(ProductState::Retired));
+    assert_eq!(retired_products.len(), 1);
+    assert_eq!(retired_products[0].metadata, metadata);
+
+    let result = pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::ProductRetired)
+    ));
+
+    let result = pool_client
+        .connect(pool_manager)
+        .set_product_metadata(product_id, metadata.clone());
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::ProductRetired)
+    ));
+
+    let result = pool_client
+        .connect(pool_manager)
+        .set_product_waiting_period(product_id, 0);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::ProductRetired)
+    ));
+
+    let coverages = pool_client.get_coverages(buyer);
+    assert_eq!(coverages.len(), 1);
+    assert!(matches!(
+        coverages[0].status,
+        pool_canister::CoverageStatus::Active
+    ));
+}
+
+#[test]
+fn test_coverage_caps_per_account_and_per_purchase() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user1 = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+    let other_account = Principal::from_slice(&[9u8; 29]);
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user1,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            pool_canister::EPISODE_DURATION * 3,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let max_per_account = Nat::from(150_000_000u64);
+    let max_per_purchase = Nat::from(100_000_000u64);
+
+    let result = pool_client.connect(buyer).set_product_coverage_limits(
+        product_id,
+        Some(max_per_account.clone()),
+        Some(max_per_purchase.clone()),
+    );
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::NotPoolManager)
+    ));
+
+    // A single purchase may not exceed what the whole account is allowed
+    let result = pool_client
+        .connect(pool_manager)
+        .set_product_coverage_limits(
+            product_id,
+            Some(max_per_purchase.clone()),
+            Some(max_per_account.clone()),
+        );
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::InvalidProductParameters)
+    ));
+
+    pool_client
+        .connect(pool_manager)
+        .set_product_coverage_limits(
+            product_id,
+            Some(max_per_account.clone()),
+            Some(max_per_purchase.clone()),
+        )
+        .unwrap();
+
+    let coverage_duration = pool_canister::EPISODE_DURATION;
+    let large_amount = Nat::from(120_000_000u64);
+    let small_amount = Nat::from(50_000_000u64);
+    let large_premium = calculate_premium(coverage_duration, annual_percent, large_amount.clone());
+    let max_premium =
+        calculate_premium(coverage_duration, annual_percent, max_per_purchase.clone());
+    let small_premium = calculate_premium(coverage_duration, annual_percent, small_amount.clone());
+
+    let quote = pool_client
+        .quote_coverage(product_id, coverage_duration, large_amount.clone(), None)
+        .unwrap();
+    assert_eq!(quote.max_coverage_amount, max_per_purchase);
+    assert!(matches!(
+        quote.error,
+        Some(pool_canister::PoolError::CoverageAmountTooLarge)
+    ));
+
+    let result = purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        large_amount,
+        large_premium,
+    );
+    assert!(result.unwrap_err().contains("CoverageAmountTooLarge"));
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        max_per_purchase.clone(),
+        max_premium.clone(),
+    )
+    .expect("Purchase up to the per-purchase cap should succeed");
+
+    // Further cover for the same account is limited by what it already holds
+    let result = purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        max_per_purchase.clone(),
+        max_premium.clone(),
+    );
+    assert!(result.unwrap_err().contains("AccountCoverageLimitExceeded"));
+
+    // Quotes for a covered account apply its remaining allowance
+    let quote = pool_client
+        .quote_coverage(
+            product_id,
+            coverage_duration,
+            max_per_purchase.clone(),
+            Some(buyer),
+        )
+        .unwrap();
+    assert_eq!(
+        quote.max_coverage_amount,
+        max_per_account.clone() - max_per_purchase.clone()
+    );
+    assert!(matches!(
+        quote.error,
+        Some(pool_canister::PoolError::AccountCoverageLimitExceeded)
+    ));
+    let quote = pool_client
+        .quote_coverage(
+            product_id,
+            coverage_duration,
+            max_per_purchase.clone(),
+            Some(other_account),
+        )
+        .unwrap();
+    assert_eq!(quote.max_coverage_amount, max_per_purchase);
+    assert!(quote.error.is_none());
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        small_amount.clone(),
+        small_premium.clone(),
+    )
+    .expect("Purchase within the per-account cap should succeed");
+
+    // Other covered accounts have their own allowance
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        other_account,
+        coverage_duration,
+        max_per_purchase.clone(),
+        max_premium,
+    )
+    .expect("Purchase for another covered account should succeed");
+
+    let result = purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        small_amount.clone(),
+        small_premium.clone(),
+    );
+    assert!(result.unwrap_err().contains("AccountCoverageLimitExceeded"));
+
+    // Cancelled coverages no longer count towards the account's cap
+    let small_coverage = pool_client
+        .get_coverages(buyer)
+        .into_iter()
+        .find(|coverage| coverage.coverage_amount == small_amount)
+        .unwrap();
+    pool_client
+        .connect(buyer)
+        .cancel_coverage(small_coverage.coverage_id)
+        .unwrap();
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        small_amount,
+        small_premium,
+    )
+    .expect("Purchase should succeed once earlier cover is cancelled");
 }
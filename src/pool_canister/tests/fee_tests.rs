@@ -0,0 +1,259 @@
+use candid::{Nat, Principal};
+use commons::{
+    calculate_premium, create_deposit, get_stakable_episode_with_client, purchase_coverage,
+    transfer_to_subaccount, LedgerCanisterClient, PoolCanisterClient, TRANSFER_FEE,
+};
+use pool_canister::{Account, PoolError, ProductState, EPISODE_DURATION};
+
+mod setup;
+use setup::setup;
+
+#[test]
+fn test_premium_fees_accrue_and_are_claimable() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let executor = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+    let treasury = Principal::from_slice(&[9u8; 29]);
+    let deployer = Principal::from_slice(&[7u8; 29]);
+    let protocol_owner = Principal::from_slice(&[8u8; 29]);
+
+    // The protocol fee is governed by the protocol owner, the manager fee by the pool manager
+    assert_eq!(pool_client.get_protocol_owner(), deployer);
+
+    let result = pool_client
+        .connect(pool_manager)
+        .set_protocol_owner(pool_manager);
+    assert!(matches!(result, Err(PoolError::NotOwner)));
+
+    pool_client
+        .connect(deployer)
+        .set_protocol_owner(protocol_owner)
+        .unwrap();
+    assert_eq!(pool_client.get_protocol_owner(), protocol_owner);
+
+    for caller in [pool_manager, executor, deployer] {
+        let result = pool_client.connect(caller).set_protocol_fee(treasury, 1000);
+        assert!(matches!(result, Err(PoolError::NotOwner)));
+    }
+
+    pool_client
+        .connect(protocol_owner)
+        .set_protocol_fee(treasury, 1000)
+        .unwrap();
+
+    let result = pool_client.connect(executor).set_manager_fee(500);
+    assert!(matches!(result, Err(PoolError::NotPoolManager)));
+
+    let result = pool_client.connect(pool_manager).set_manager_fee(9000);
+    assert!(matches!(result, Err(PoolError::InvalidFee)));
+
+    pool_client
+        .connect(pool_manager)
+        .set_manager_fee(500)
+        .unwrap();
+
+    let fee_config = pool_client.get_fee_config();
+    assert_eq!(fee_config.protocol_treasury, treasury);
+    assert_eq!(fee_config.protocol_fee_bps, 1000);
+    assert_eq!(fee_config.manager_fee_bps, 500);
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        executor,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            EPISODE_DURATION * 3,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let coverage_amount = Nat::from(100_000_000u64);
+    let premium_amount =
+        calculate_premium(EPISODE_DURATION, annual_percent, coverage_amount.clone());
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        EPISODE_DURATION,
+        coverage_amount,
+        premium_amount.clone(),
+    )
+    .expect("Coverage purchase should succeed");
+
+    // Fees are taken from the premium net of the ledger fee
+    let net_premium = premium_amount.clone() - TRANSFER_FEE.clone();
+    let protocol_fee = net_premium.clone() * 1000u64 / 10_000u64;
+    let manager_fee = net_premium * 500u64 / 10_000u64;
+
+    let analytics = pool_client.get_pool_analytics();
+    assert_eq!(analytics.total_premiums_collected, premium_amount);
+    assert_eq!(analytics.protocol_fees_accrued, protocol_fee);
+    assert_eq!(analytics.manager_fees_accrued, manager_fee);
+    assert!(analytics.pool_reward_rate > Nat::from(0u64));
+
+    let result = pool_client.connect(buyer).claim_protocol_fees();
+    assert!(matches!(result, Err(PoolError::NotFeeRecipient)));
+
+    let claimed = pool_client.connect(treasury).claim_protocol_fees().unwrap();
+    assert_eq!(claimed, protocol_fee);
+    assert_eq!(
+        ledger_client.icrc1_balance_of(Account {
+            owner: treasury,
+            subaccount: None,
+        }),
+        protocol_fee - TRANSFER_FEE.clone()
+    );
+
+    let claimed = pool_client
+        .connect(pool_manager)
+        .claim_manager_fees()
+        .unwrap();
+    assert_eq!(claimed, manager_fee);
+
+    let analytics = pool_client.get_pool_analytics();
+    assert_eq!(analytics.protocol_fees_accrued, Nat::from(0u64));
+    assert_eq!(analytics.manager_fees_accrued, Nat::from(0u64));
+
+    // Nothing is left to claim until the next premium comes in
+    let result = pool_client.connect(treasury).claim_protocol_fees();
+    assert!(matches!(result, Err(PoolError::InsufficientBalance)));
+}
+
+#[test]
+fn test_referrer_commissions_accrue_and_are_withdrawable() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+    let referrer = Principal::from_slice(&[10u8; 29]);
+    let unknown_referrer = Principal::from_slice(&[11u8; 29]);
+
+    let result = pool_client.connect(user).register_referrer(referrer, 1000);
+    assert!(matches!(result, Err(PoolError::NotPoolManager)));
+
+    let result = pool_client
+        .connect(pool_manager)
+        .register_referrer(referrer, 10_000);
+    assert!(matches!(result, Err(PoolError::InvalidFee)));
+
+    pool_client
+        .connect(pool_manager)
+        .register_referrer(referrer, 1000)
+        .unwrap();
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            EPISODE_DURATION * 3,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let coverage_amount = Nat::from(100_000_000u64);
+    let premium_amount =
+        calculate_premium(EPISODE_DURATION, annual_percent, coverage_amount.clone());
+
+    let purchase_subaccount = pool_client
+        .connect(buyer)
+        .get_purchase_subaccount(buyer, product_id);
+    transfer_to_subaccount(
+        &mut ledger_client,
+        buyer,
+        pool_canister,
+        purchase_subaccount,
+        premium_amount.clone(),
+    );
+
+    let result = pool_client.connect(buyer).purchase_coverage(
+        product_id,
+        buyer,
+        EPISODE_DURATION,
+        coverage_amount.clone(),
+        Some(unknown_referrer),
+    );
+    assert!(matches!(result, Err(PoolError::ReferrerNotFound)));
+
+    pool_client
+        .connect(buyer)
+        .purchase_coverage(
+            product_id,
+            buyer,
+            EPISODE_DURATION,
+            coverage_amount,
+            Some(referrer),
+        )
+        .expect("Referred purchase should succeed");
+
+    let coverage = pool_client.get_coverages(buyer)[0].clone();
+    assert_eq!(coverage.referrer, Some(referrer));
+
+    // The commission comes out of the premium before it is streamed to LPs
+    let commission = (premium_amount.clone() - TRANSFER_FEE.clone()) * 1000u64 / 10_000u64;
+    let earnings = pool_client.get_referrer(referrer).unwrap();
+    assert_eq!(earnings.commission_bps, 1000);
+    assert_eq!(earnings.commissions_accrued, commission);
+    assert_eq!(earnings.commissions_earned, commission);
+    assert_eq!(earnings.premium_volume, premium_amount);
+
+    let result = pool_client.connect(buyer).withdraw_referral_commissions();
+    assert!(matches!(result, Err(PoolError::ReferrerNotFound)));
+
+    let withdrawn = pool_client
+        .connect(referrer)
+        .withdraw_referral_commissions()
+        .unwrap();
+    assert_eq!(withdrawn, commission);
+    assert_eq!(
+        ledger_client.icrc1_balance_of(Account {
+            owner: referrer,
+            subaccount: None,
+        }),
+        commission.clone() - TRANSFER_FEE.clone()
+    );
+
+    let earnings = pool_client.get_referrer(referrer).unwrap();
+    assert_eq!(earnings.commissions_accrued, Nat::from(0u64));
+    assert_eq!(earnings.commissions_earned, commission);
+}
@@ -55,7 +55,7 @@ fn test_slash_function() {
     // Execute slash
     let result = pool_client
         .connect(executor)
-        .slash(receiver, slash_amount.clone());
+        .slash(0, receiver, slash_amount.clone());
     assert!(
         matches!(result, Ok(_)),
         "Slash should succeed: {:?}",
@@ -159,3 +159,91 @@ fn test_slash_function() {
         "Receiver should have received actual accumulated slashed tokens minus fees"
     );
 }
+
+#[test]
+fn test_slash_is_idempotent_per_claim() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let executor = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let receiver = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+    let deposit_amount = Nat::from(300_000_000u64);
+    let slash_amount = Nat::from(100_000_000u64);
+    let claim_id = 7u64;
+
+    let episode = commons::get_stakable_episode_with_client(&pool_client, 0);
+    commons::create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user,
+        deposit_amount,
+        episode,
+    )
+    .expect("Deposit should succeed");
+
+    assert!(
+        pool_client.get_slash_record(claim_id).is_none(),
+        "No slash record should exist before the first slash"
+    );
+
+    pool_client
+        .connect(executor)
+        .slash(claim_id, receiver, slash_amount.clone())
+        .expect("First slash should succeed");
+
+    let pool_after_first = pool_client.get_pool_state();
+    let receiver_account = Account {
+        owner: receiver,
+        subaccount: None,
+    };
+    let receiver_balance_after_first = ledger_client.icrc1_balance_of(receiver_account.clone());
+
+    let record = pool_client
+        .get_slash_record(claim_id)
+        .expect("Slash record should be stored");
+    assert_eq!(record.status, pool_canister::SlashStatus::Completed);
+    assert_eq!(record.receiver, receiver);
+    assert_eq!(record.amount, slash_amount);
+
+    // Repeating the call with the same claim id returns the prior result without slashing again
+    let repeat_result = pool_client
+        .connect(executor)
+        .slash(claim_id, receiver, slash_amount.clone());
+    assert!(
+        matches!(repeat_result, Ok(())),
+        "Repeated slash should return the prior result: {:?}",
+        repeat_result
+    );
+
+    let pool_after_repeat = pool_client.get_pool_state();
+    assert_eq!(
+        pool_after_repeat.total_assets, pool_after_first.total_assets,
+        "Repeated slash must not reduce pool assets again"
+    );
+    assert_eq!(
+        ledger_client.icrc1_balance_of(receiver_account),
+        receiver_balance_after_first,
+        "Repeated slash must not transfer funds again"
+    );
+
+    // Only slashes stuck in progress can be resolved, and only by the pool manager
+    let pool_manager = receiver;
+    let result = pool_client.connect(executor).resolve_slash(claim_id, true);
+    assert!(matches!(result, Err(pool_canister::PoolError::NotPoolManager)));
+
+    let result = pool_client.connect(pool_manager).resolve_slash(claim_id, false);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::SlashNotInProgress)
+    ));
+
+    let result = pool_client
+        .connect(pool_manager)
+        .resolve_slash(claim_id + 1, true);
+    assert!(matches!(
+        result,
+        Err(pool_canister::PoolError::SlashRecordNotFound)
+    ));
+}
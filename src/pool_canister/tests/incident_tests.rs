@@ -0,0 +1,236 @@
+use candid::{Nat, Principal};
+use commons::{
+    calculate_premium, create_deposit, get_current_time, get_stakable_episode_with_client,
+    purchase_coverage, LedgerCanisterClient, PoolCanisterClient,
+};
+use pool_canister::{IncidentSeverity, IncidentStatus, PoolError, ProductState};
+
+mod setup;
+use setup::setup;
+
+#[test]
+fn test_incident_registry_freezes_purchases() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+    let oracle = Principal::from_slice(&[7u8; 29]);
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            pool_canister::EPISODE_DURATION * 6,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+    let other_product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Lending Insurance".to_string(),
+            annual_percent,
+            pool_canister::EPISODE_DURATION * 6,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(other_product_id, ProductState::Active)
+        .unwrap();
+
+    let incident_time = get_current_time(&pic);
+
+    // Only the pool manager and the configured oracle may report incidents
+    let result = pool_client.connect(oracle).report_incident(
+        product_id,
+        incident_time,
+        IncidentSeverity::High,
+    );
+    assert!(matches!(result, Err(PoolError::NotIncidentReporter)));
+
+    let result = pool_client.connect(oracle).set_oracle_principal(oracle);
+    assert!(matches!(result, Err(PoolError::NotPoolManager)));
+
+    pool_client
+        .connect(pool_manager)
+        .set_oracle_principal(oracle)
+        .expect("Pool manager should set the oracle");
+    assert_eq!(pool_client.get_oracle_principal(), oracle);
+
+    let result = pool_client.connect(oracle).report_incident(
+        product_id,
+        incident_time + 60,
+        IncidentSeverity::High,
+    );
+    assert!(matches!(result, Err(PoolError::InvalidIncidentTime)));
+
+    let result =
+        pool_client
+            .connect(oracle)
+            .report_incident(999, incident_time, IncidentSeverity::High);
+    assert!(matches!(result, Err(PoolError::ProductNotFound)));
+
+    let incident_id = pool_client
+        .connect(oracle)
+        .report_incident(product_id, incident_time, IncidentSeverity::Critical)
+        .expect("Oracle should report the incident");
+
+    let incident = pool_client.get_incident(incident_id).unwrap();
+    assert_eq!(incident.product_id, product_id);
+    assert_eq!(incident.incident_time, incident_time);
+    assert_eq!(incident.severity, IncidentSeverity::Critical);
+    assert_eq!(incident.status, IncidentStatus::Open);
+    assert_eq!(incident.reported_by, oracle);
+    assert_eq!(pool_client.get_product_incidents(product_id).len(), 1);
+    assert!(pool_client
+        .get_product_incidents(other_product_id)
+        .is_empty());
+
+    // Purchases of the affected product are frozen, other products are unaffected
+    let coverage_duration = pool_canister::EPISODE_DURATION * 3;
+    let coverage_amount = Nat::from(100_000_000u64);
+    let premium_amount =
+        calculate_premium(coverage_duration, annual_percent, coverage_amount.clone());
+
+    let result = purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        coverage_amount.clone(),
+        premium_amount.clone(),
+    );
+    assert!(
+        result.unwrap_err().contains("ProductFrozen"),
+        "Purchases of a product with an open incident should be frozen"
+    );
+
+    let result = purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        other_product_id,
+        buyer,
+        coverage_duration,
+        coverage_amount.clone(),
+        premium_amount.clone(),
+    );
+    assert!(result.is_ok(), "Unaffected product should stay purchasable");
+
+    // Resolving the incident lifts the freeze
+    pool_client
+        .connect(pool_manager)
+        .resolve_incident(incident_id)
+        .expect("Pool manager should resolve the incident");
+    let incident = pool_client.get_incident(incident_id).unwrap();
+    assert_eq!(incident.status, IncidentStatus::Resolved);
+    assert!(incident.resolved_at.is_some());
+
+    let result = pool_client.connect(oracle).resolve_incident(incident_id);
+    assert!(matches!(result, Err(PoolError::IncidentAlreadyResolved)));
+
+    let result = purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        coverage_amount,
+        premium_amount,
+    );
+    assert!(result.is_ok(), "Purchase should succeed after resolution");
+}
+
+#[test]
+fn test_open_incident_blocks_coverage_cancellation() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            pool_canister::EPISODE_DURATION * 6,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let coverage_duration = pool_canister::EPISODE_DURATION * 3;
+    let coverage_amount = Nat::from(100_000_000u64);
+    let premium_amount =
+        calculate_premium(coverage_duration, annual_percent, coverage_amount.clone());
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        coverage_duration,
+        coverage_amount,
+        premium_amount,
+    )
+    .expect("Coverage purchase should succeed");
+    let coverage_id = pool_client.get_coverages(buyer)[0].coverage_id;
+
+    let incident_id = pool_client
+        .connect(pool_manager)
+        .report_incident(product_id, get_current_time(&pic), IncidentSeverity::High)
+        .expect("Pool manager should report the incident");
+
+    // Cover cannot be cancelled for a refund while the incident is open
+    let result = pool_client.connect(buyer).cancel_coverage(coverage_id);
+    assert!(matches!(result, Err(PoolError::ProductFrozen)));
+
+    pool_client
+        .connect(pool_manager)
+        .resolve_incident(incident_id)
+        .expect("Pool manager should resolve the incident");
+
+    pool_client
+        .connect(buyer)
+        .cancel_coverage(coverage_id)
+        .expect("Cancellation should succeed after resolution");
+}
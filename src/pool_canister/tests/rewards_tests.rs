@@ -625,7 +625,7 @@ fn test_slashing_during_reward_distribution() {
     // Perform slashing
     pool_client
         .connect(executor)
-        .slash(slash_receiver, slash_amount.clone())
+        .slash(0, slash_receiver, slash_amount.clone())
         .expect("Slashing should succeed");
 
     // Check rewards immediately after slashing - they should be the same
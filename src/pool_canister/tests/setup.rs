@@ -1,12 +1,11 @@
 use candid::{encode_args, Nat, Principal};
-use pool_canister::{Account, TRANSFER_FEE};
 use pocket_ic::PocketIc;
+use pool_canister::{Account, TRANSFER_FEE};
 
 #[path = "types.rs"]
 mod ledger_types;
 use ledger_types::{ArchiveOptions, FeatureFlags, InitArgs, LedgerArg};
 
-
 const ICRC1_LEDGER_WASM_PATH: &str = "../../ic-icrc1-ledger.wasm";
 const WASM_PATH: &str = "../../target/wasm32-unknown-unknown/release/pool_canister.wasm";
 
@@ -95,8 +94,9 @@ pub fn setup() -> (PocketIc, Principal, Principal) {
         None,
     );
 
-    // Create and setup main canister with ledger_id
-    let canister_id = pic.create_canister();
+    // Create and setup main canister with ledger_id, deployed by the protocol owner
+    let protocol_owner = Principal::from_slice(&[7u8; 29]);
+    let canister_id = pic.create_canister_with_settings(Some(protocol_owner), None);
     pic.add_cycles(canister_id, 2_000_000_000_000);
     let wasm = std::fs::read(WASM_PATH)
         .expect("Build first: cargo build --target wasm32-unknown-unknown --release");
@@ -105,7 +105,7 @@ pub fn setup() -> (PocketIc, Principal, Principal) {
     let executor = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
     let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
     let init_args = encode_args((ledger_id, executor, pool_manager)).unwrap();
-    pic.install_canister(canister_id, wasm, init_args, None);
+    pic.install_canister(canister_id, wasm, init_args, Some(protocol_owner));
 
     (pic, canister_id, ledger_id)
 }
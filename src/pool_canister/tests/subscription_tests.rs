@@ -0,0 +1,167 @@
+use candid::{Nat, Principal};
+use commons::clients::ledger::{ApproveArgs, ApproveResult};
+use commons::{
+    advance_time, calculate_premium, create_deposit, get_current_time,
+    get_stakable_episode_with_client, purchase_coverage, LedgerCanisterClient, PoolCanisterClient,
+};
+use pocket_ic::PocketIc;
+use pool_canister::{Account, PoolError, ProductState, SubscriptionStatus, EPISODE_DURATION};
+
+mod setup;
+use setup::setup;
+
+// Moves just past the next episode boundary and lets the episode timer run its renewals.
+fn advance_to_next_episode(pic: &PocketIc) {
+    let current_time = get_current_time(pic);
+    let next_episode_start = (current_time / EPISODE_DURATION + 1) * EPISODE_DURATION;
+    advance_time(pic, next_episode_start - current_time + 1);
+    for _ in 0..5 {
+        pic.tick();
+    }
+}
+
+fn approve_pool(
+    ledger_client: &mut LedgerCanisterClient,
+    owner: Principal,
+    pool_canister: Principal,
+    amount: Nat,
+) {
+    let result = ledger_client.connect(owner).icrc2_approve(ApproveArgs {
+        from_subaccount: None,
+        spender: Account {
+            owner: pool_canister,
+            subaccount: None,
+        },
+        amount,
+        expected_allowance: None,
+        expires_at: None,
+        fee: None,
+        memo: None,
+        created_at_time: None,
+    });
+    assert!(matches!(result, ApproveResult::Ok(_)));
+}
+
+#[test]
+fn test_subscription_renews_each_episode_and_lapses_without_allowance() {
+    let (pic, pool_canister, ledger_id) = setup();
+    let mut pool_client = PoolCanisterClient::new(&pic, pool_canister);
+    let mut ledger_client = LedgerCanisterClient::new(&pic, ledger_id);
+
+    let user = Principal::from_text("rdmx6-jaaaa-aaaaa-aaadq-cai").unwrap();
+    let buyer = Principal::from_text("xkbqi-2qaaa-aaaah-qbpqq-cai").unwrap();
+    let pool_manager = Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap();
+
+    let current_episode = get_stakable_episode_with_client(&pool_client, 3);
+    create_deposit(
+        &mut pool_client,
+        &mut ledger_client,
+        user,
+        Nat::from(1_000_000_000u64),
+        current_episode,
+    )
+    .expect("Deposit should succeed");
+
+    let annual_percent = 500u64;
+    let product_id = pool_client
+        .connect(pool_manager)
+        .create_product(
+            "Bridge Insurance".to_string(),
+            annual_percent,
+            EPISODE_DURATION * 6,
+            5000u64,
+        )
+        .unwrap();
+    pool_client
+        .connect(pool_manager)
+        .set_product_state(product_id, ProductState::Active)
+        .unwrap();
+
+    let coverage_amount = Nat::from(100_000_000u64);
+    let period_premium =
+        calculate_premium(EPISODE_DURATION, annual_percent, coverage_amount.clone());
+
+    purchase_coverage(
+        &mut pool_client,
+        &mut ledger_client,
+        buyer,
+        product_id,
+        buyer,
+        EPISODE_DURATION,
+        coverage_amount.clone(),
+        period_premium.clone(),
+    )
+    .expect("Coverage purchase should succeed");
+
+    let coverage = pool_client.get_coverages(buyer)[0].clone();
+
+    let result = pool_client
+        .connect(user)
+        .subscribe_coverage(coverage.coverage_id);
+    assert!(matches!(result, Err(PoolError::NotCoverageBuyer)));
+
+    approve_pool(
+        &mut ledger_client,
+        buyer,
+        pool_canister,
+        Nat::from(1_000_000_000u64),
+    );
+    pool_client
+        .connect(buyer)
+        .subscribe_coverage(coverage.coverage_id)
+        .expect("Buyer should subscribe");
+
+    let result = pool_client
+        .connect(buyer)
+        .subscribe_coverage(coverage.coverage_id);
+    assert!(matches!(result, Err(PoolError::SubscriptionAlreadyActive)));
+
+    // The next period is pulled from the allowance one episode ahead of the expiry
+    advance_to_next_episode(&pic);
+
+    let renewed = pool_client.get_coverage(coverage.coverage_id).unwrap();
+    assert_eq!(renewed.end_time, coverage.end_time + EPISODE_DURATION);
+    assert_eq!(
+        renewed.claim_deadline,
+        coverage.claim_deadline + EPISODE_DURATION
+    );
+    assert_eq!(
+        renewed.premium_amount,
+        coverage.premium_amount.clone() + period_premium.clone()
+    );
+
+    let subscription = pool_client.get_subscription(coverage.coverage_id).unwrap();
+    assert_eq!(subscription.status, SubscriptionStatus::Active);
+    assert!(subscription.last_payment_at.is_some());
+    assert!(subscription.past_due_since.is_none());
+
+    // Without an allowance the subscription falls past due while the paid period still runs
+    approve_pool(&mut ledger_client, buyer, pool_canister, Nat::from(0u64));
+    advance_to_next_episode(&pic);
+
+    let coverage = pool_client.get_coverage(coverage.coverage_id).unwrap();
+    assert_eq!(coverage.end_time, renewed.end_time);
+    assert!(get_current_time(&pic) < coverage.end_time);
+
+    let subscription = pool_client.get_subscription(coverage.coverage_id).unwrap();
+    assert_eq!(subscription.status, SubscriptionStatus::PastDue);
+    assert!(subscription.past_due_since.is_some());
+
+    // Once the paid period is over the subscription lapses and the coverage simply expires
+    advance_to_next_episode(&pic);
+
+    let subscription = pool_client.get_subscription(coverage.coverage_id).unwrap();
+    assert_eq!(subscription.status, SubscriptionStatus::Lapsed);
+    assert_eq!(
+        pool_client
+            .get_coverage(coverage.coverage_id)
+            .unwrap()
+            .end_time,
+        renewed.end_time
+    );
+
+    let result = pool_client
+        .connect(buyer)
+        .cancel_subscription(coverage.coverage_id);
+    assert!(matches!(result, Err(PoolError::SubscriptionNotActive)));
+}